@@ -9,26 +9,301 @@ use core::{mem, task, fmt};
 
 pub use http_body::{Frame, SizeHint};
 pub use http_body::Body as HttpBody;
+#[cfg(feature = "stream")]
+pub use futures_core::Stream;
+
+mod cache_key;
+pub use cache_key::CacheKeyBody;
+mod asset;
+pub use asset::{AssetBody, AssetMap};
+mod range;
+pub use range::{ContentRange, RangeError};
+mod poll_limit;
+pub use poll_limit::{PollLimitBody, PollLimitError};
+mod rope;
+pub use rope::{Rope, RopeCollector};
+mod tap;
+pub use tap::TapBody;
+mod map;
+pub use map::MapBody;
+mod rechunk;
+pub use rechunk::RechunkBody;
+mod repeat;
+pub use repeat::RepeatBody;
+mod take;
+pub use take::TakeBody;
+mod keep_alive;
+pub use keep_alive::KeepAliveBody;
+mod emit_deadline;
+pub use emit_deadline::{EmitDeadlineBody, EmitDeadlineError};
+mod timeout;
+pub use timeout::CollectTimeout;
+mod length_check;
+pub use length_check::{LengthCheckBody, LENGTH_ACTUAL, LENGTH_MATCH};
+mod passthrough;
+pub use passthrough::{FrameHook, PassthroughBody};
+mod framed;
+pub use framed::{FramedCollector, FramedError, LengthPrefix};
+mod lines;
+pub use lines::{LineError, LineLengthCollector, LineLimitCollector, LineStream, LineStreamError};
+mod split;
+pub use split::{SplitCollect, SplitCollector, SplitError};
+mod offsets;
+pub use offsets::FrameOffsetsCollector;
+mod trailers;
+pub use trailers::TrailersCollector;
+mod preview;
+pub use preview::{PreviewCollect, PreviewCollector};
+mod collect_all;
+pub use collect_all::CollectAll;
+mod before_first;
+pub use before_first::CollectBeforeFirstFrame;
+mod inspect;
+pub use inspect::CollectInspect;
+mod windows;
+pub use windows::CollectWindows;
+mod retry;
+pub use retry::RetryBody;
+mod overflow;
+pub use overflow::{OverflowPolicy, OverflowPolicyError};
+mod multi;
+pub use multi::MultiBody;
+mod chain;
+pub use chain::{ChainBody, ChainError};
+mod multipart;
+pub use multipart::{MultipartBody, MultipartError};
+mod charset;
+pub use charset::{content_type_charset, Charset, CharsetError, TextCollector};
+mod array;
+pub use array::{ArrayCollector, ArrayError};
+mod arena;
+pub use arena::{ChunkedArena, ChunkedArenaCollector};
+mod decode;
+pub use decode::DecodeCollector;
+mod form;
+pub use form::{parse_form, FormCollector, FormError};
+mod string;
+pub use string::{StringCollector, StringError};
+mod json;
+pub use json::{JsonError, JsonValidateCollector};
+mod header_block;
+pub use header_block::{HeaderBlockCollector, HeaderBlockError};
+mod sniff;
+pub use sniff::{sniff, MimeSniffCollector, SniffCollector, SniffError, SNIFF_LEN};
+#[cfg(feature = "std")]
+mod channel;
+#[cfg(feature = "std")]
+mod sse;
+#[cfg(feature = "std")]
+pub use sse::{SseBody, SseSender, SseError};
+#[cfg(feature = "std")]
+mod channel_body;
+#[cfg(feature = "std")]
+pub use channel_body::{ChannelBody, ChannelSender};
+#[cfg(feature = "std")]
+mod spill;
+#[cfg(feature = "std")]
+pub use spill::{SpillCollector, Spilled, SpilledFile};
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "std")]
+pub use shared::SharedCollector;
+#[cfg(feature = "std")]
+mod write;
+#[cfg(feature = "std")]
+pub use write::WriteCollector;
+#[cfg(feature = "std")]
+mod throttle;
+#[cfg(feature = "std")]
+pub use throttle::{SharedRateLimiter, ThrottledBody};
+#[cfg(feature = "std")]
+mod min_rate;
+#[cfg(feature = "std")]
+pub use min_rate::CollectMinRate;
+#[cfg(feature = "hash")]
+mod hash_trailers;
+#[cfg(feature = "hash")]
+pub use hash_trailers::{HashTrailersBody, CHECKSUM_SHA256};
+#[cfg(feature = "hash")]
+mod digest;
+#[cfg(feature = "hash")]
+pub use digest::HashCollector;
+#[cfg(feature = "hash")]
+mod digest_verify;
+#[cfg(feature = "hash")]
+pub use digest_verify::{DigestVerifyCollector, DigestError};
+#[cfg(feature = "hash")]
+mod chunking;
+#[cfg(feature = "hash")]
+pub use chunking::ChunkingCollector;
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "csv")]
+pub use csv::{CsvCollector, CsvError, CsvRecords};
+#[cfg(feature = "json")]
+mod json_decode;
+#[cfg(feature = "json")]
+pub use json_decode::JsonCollector;
+#[cfg(feature = "gzip")]
+mod inflate;
+#[cfg(feature = "lz4")]
+mod lz4;
+#[cfg(feature = "compress")]
+mod compress;
+#[cfg(feature = "compress")]
+pub use compress::{CompressBody, CompressError};
+#[cfg(any(feature = "compress", feature = "gzip"))]
+mod decompress_body;
+#[cfg(any(feature = "compress", feature = "gzip"))]
+pub use decompress_body::{DecompressBody, DecompressBodyError};
+#[cfg(any(feature = "compress", feature = "gzip"))]
+mod decompress_progress;
+#[cfg(any(feature = "compress", feature = "gzip"))]
+pub use decompress_progress::DecompressProgressCollector;
+#[cfg(feature = "aead")]
+mod decrypt;
+#[cfg(feature = "aead")]
+pub use decrypt::{Aead, DecryptCollector, DecryptError};
 
-#[repr(transparent)]
 ///HTTP body
+///
+///Cloning is cheap, as content is reference counted, hence body can be re-sent as it is (e.g. on retry).
+#[derive(Clone)]
 pub struct Body {
     inner: bytes::Bytes,
+    trailers: Option<http::HeaderMap>,
 }
 
 impl Body {
     ///Creates new instance
     pub const fn new(inner: bytes::Bytes) -> Self {
         Self {
-            inner
+            inner,
+            trailers: None,
         }
     }
 
+    #[inline(always)]
+    ///Sets trailers, emitted as separate frame after data.
+    ///
+    ///While trailers are pending, `size_hint` reports only lower bound,
+    ///as exact size makes `hyper` send `Content-Length` and drop trailers over `HTTP/1.1`.
+    pub fn with_trailers(mut self, headers: http::HeaderMap) -> Self {
+        self.trailers = Some(headers);
+        self
+    }
+
     #[inline(always)]
     ///Creates empty body
     pub const fn empty() -> Self {
         Self::new(bytes::Bytes::new())
     }
+
+    #[inline(always)]
+    ///Creates body out of multiple `chunks`, emitting each as separate frame.
+    ///
+    ///Unlike `Body`, preserves framing, without concatenating chunks. See `MultiBody` for details.
+    pub fn from_chunks(chunks: Vec<bytes::Bytes>) -> MultiBody {
+        MultiBody::from(chunks)
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes remaining in body
+    pub const fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    ///Returns whether no data remains in body.
+    ///
+    ///Trailers are not taken into account.
+    pub const fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline(always)]
+    ///Access remaining content
+    pub const fn as_bytes(&self) -> &bytes::Bytes {
+        &self.inner
+    }
+
+    #[inline(always)]
+    ///Returns remaining content, dropping trailers if any
+    pub fn into_bytes(self) -> bytes::Bytes {
+        self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl Body {
+    #[inline]
+    ///Creates body by reading `reader` to the end.
+    ///
+    ///Whole content is buffered in memory, hence it is only suitable for small payloads.
+    ///Use `ChannelBody` to stream content instead.
+    pub fn from_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+        Ok(Self::from(buffer))
+    }
+}
+
+#[cfg(feature = "hash")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///ETag validator kind
+pub enum ETagKind {
+    ///Strong validator, formatted as `"<digest>"`
+    Strong,
+    ///Weak validator, formatted as `W/"<digest>"`
+    Weak,
+}
+
+#[cfg(feature = "hash")]
+impl Body {
+    #[inline]
+    ///Creates body alongside with its strong ETag.
+    ///
+    ///ETag is base64 encoded `SHA-256` digest of `bytes`, formatted as `"<digest>"`.
+    ///
+    ///Use `with_etag_by` to select other hash function or validator kind.
+    pub fn with_etag(bytes: bytes::Bytes) -> (Self, String) {
+        Self::with_etag_by::<crate::hash::Sha256>(bytes, ETagKind::Strong)
+    }
+
+    ///Creates body alongside with its ETag, computed as base64 encoded digest `D` of `bytes`.
+    ///
+    ///Format is determined by `kind`:
+    ///- `Strong` - `"<digest>"`
+    ///- `Weak` - `W/"<digest>"`
+    pub fn with_etag_by<D: crate::hash::Digest>(bytes: bytes::Bytes, kind: ETagKind) -> (Self, String) {
+        let digest = D::digest(&bytes);
+
+        let mut etag = String::new();
+        if let ETagKind::Weak = kind {
+            etag.push_str("W/");
+        }
+        etag.push('"');
+        crate::hash::base64_encode(&digest, &mut etag);
+        etag.push('"');
+
+        (Self::new(bytes), etag)
+    }
+}
+
+impl AsRef<[u8]> for Body {
+    #[inline(always)]
+    fn as_ref(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
+impl core::ops::Deref for Body {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
 }
 
 impl From<Vec<u8>> for Body {
@@ -63,60 +338,311 @@ impl HttpBody for Body {
             mem::swap(&mut self.inner, &mut result);
             task::Poll::Ready(Some(Ok(Frame::data(result))))
         } else {
-            task::Poll::Ready(None)
+            task::Poll::Ready(self.trailers.take().map(|trailers| Ok(Frame::trailers(trailers))))
         }
     }
 
     #[inline(always)]
     fn is_end_stream(&self) -> bool {
-        self.inner.is_empty()
+        self.inner.is_empty() && self.trailers.is_none()
     }
 
     #[inline(always)]
     fn size_hint(&self) -> SizeHint {
-        SizeHint::with_exact(self.inner.len() as u64)
+        match self.trailers {
+            Some(_) => {
+                let mut hint = SizeHint::new();
+                hint.set_lower(self.inner.len() as u64);
+                hint
+            },
+            None => SizeHint::with_exact(self.inner.len() as u64),
+        }
     }
 }
 
 impl fmt::Debug for Body {
     #[inline(always)]
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt.debug_struct("Body").field("len", &self.inner.len()).finish()
+        fmt.debug_struct("Body").field("len", &self.inner.len()).field("trailers", &self.trailers.is_some()).finish()
+    }
+}
+
+static NOOP_WAKER_VTABLE: task::RawWakerVTable = task::RawWakerVTable::new(noop_waker_clone, noop_waker_action, noop_waker_action, noop_waker_action);
+
+fn noop_waker_clone(_: *const ()) -> task::RawWaker {
+    task::RawWaker::new(core::ptr::null(), &NOOP_WAKER_VTABLE)
+}
+
+fn noop_waker_action(_: *const ()) {
+}
+
+///Polls `fut` once with no-op waker, returning `None` if it is pending.
+///
+///Only suitable for futures that never return pending, such as `Collect` over `Body`, as there is nothing to wake.
+///Pending future is dropped.
+pub fn block_on_ready<F: Future + Unpin>(mut fut: F) -> Option<F::Output> {
+    //Vtable functions do nothing, so there is no contract to uphold
+    let waker = unsafe {
+        task::Waker::from_raw(noop_waker_clone(core::ptr::null()))
+    };
+    let mut ctx = task::Context::from_waker(&waker);
+    match Future::poll(Pin::new(&mut fut), &mut ctx) {
+        task::Poll::Ready(result) => Some(result),
+        task::Poll::Pending => None,
+    }
+}
+
+#[inline(always)]
+///Returns exact number of bytes remaining in `body`, as reported by `SizeHint::exact()`.
+///
+///Returns `None` for streaming bodies of unknown length, in which case `Content-Length` must not be set.
+pub fn content_length<T: HttpBody>(body: &T) -> Option<u64> {
+    body.size_hint().exact()
+}
+
+#[inline]
+///Creates `200 OK` response with `body`, setting `Content-Length` if body length is known.
+pub fn into_response<T: HttpBody>(body: T) -> http::Response<T> {
+    let content_length = content_length(&body);
+    let mut response = http::Response::new(body);
+    if let Some(content_length) = content_length {
+        response.headers_mut().insert(http::header::CONTENT_LENGTH, content_length.into());
+    }
+    response
+}
+
+#[inline]
+///Creates `304 Not Modified` response.
+///
+///As per RFC 9110, such response carries neither body nor `Content-Length`.
+///Validator headers (e.g. `ETag`) should be set by caller, if needed.
+pub fn not_modified() -> http::Response<Body> {
+    let mut response = http::Response::new(Body::empty());
+    *response.status_mut() = http::StatusCode::NOT_MODIFIED;
+    response
+}
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper carrying arbitrary typed metadata via `http::Extensions`
+    ///
+    ///Frames are delegated to underlying body as it is.
+    #[project = BodyWithExtensionsProj]
+    pub struct BodyWithExtensions<T = Body> {
+        #[pin]
+        body: T,
+        extensions: http::Extensions,
+    }
+}
+
+impl<T> BodyWithExtensions<T> {
+    #[inline(always)]
+    ///Creates new instance with empty extensions
+    pub fn new(body: T) -> Self {
+        Self {
+            body,
+            extensions: http::Extensions::new(),
+        }
+    }
+
+    #[inline(always)]
+    ///Access body's extensions
+    pub fn extensions(&self) -> &http::Extensions {
+        &self.extensions
+    }
+
+    #[inline(always)]
+    ///Access body's extensions mutably
+    pub fn extensions_mut(&mut self) -> &mut http::Extensions {
+        &mut self.extensions
+    }
+
+    #[inline(always)]
+    ///Access underlying body
+    pub fn get_ref(&self) -> &T {
+        &self.body
+    }
+
+    #[inline(always)]
+    ///Splits into underlying body and extensions
+    pub fn into_parts(self) -> (T, http::Extensions) {
+        (self.body, self.extensions)
+    }
+}
+
+impl<T: HttpBody> HttpBody for BodyWithExtensions<T> {
+    type Data = T::Data;
+    type Error = T::Error;
+
+    #[inline(always)]
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let BodyWithExtensionsProj { body, .. } = self.project();
+        HttpBody::poll_frame(body, cx)
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BodyWithExtensions<T> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("BodyWithExtensions").field("body", &self.body).field("extensions", &self.extensions).finish()
     }
 }
 
 ///Possible errors from `Collector`
+///
+///`O` is output of collector, which is returned on overflow.
 #[derive(Debug)]
-pub enum CollectError<T, C> {
+pub enum CollectError<T, C, O = ()> {
     ///Underlying error from Body
     Transport(T),
-    ///Error accumulating body
-    Collector(C),
-    ///Body is over limit
-    Overflow,
+    ///Collector failed to append data, which usually indicates invalid input
+    AppendError(C),
+    ///Collector failed to produce output once body ended
+    ConsumeError(C),
+    ///Body is over limit.
+    ///
+    ///Contains output of collector over data received up to the limit, including prefix of frame that caused overflow.
+    ///It is `None` if collector cannot produce output from partial data (e.g. it is incomplete), or if overflow is detected from `Content-Length`.
+    Overflow(Option<O>),
+    ///Body sent more data frames than allowed by `Collect::with_max_frames`
+    TooManyFrames,
+    ///Body ended before delivering its `SizeHint::lower()` bytes
+    Truncated {
+        ///Lower bound reported by body
+        expected_min: u64,
+        ///Number of bytes actually received
+        actual: u64,
+    },
+    ///Body throughput is below required minimum
+    TooSlow {
+        ///Number of bytes received
+        received: u64,
+        ///Time elapsed since start of collection
+        elapsed: core::time::Duration,
+    },
+    ///Number of bytes received differs from declared exact length
+    LengthMismatch {
+        ///Declared length
+        expected: u64,
+        ///Number of bytes actually received, which may be partial if body delivered more than expected
+        actual: u64,
+    },
+    ///Body is not received completely within timeout
+    Timeout {
+        ///Number of bytes received
+        received: u64,
+    },
 }
 
-impl<T, C> CollectError<T, C> {
+impl<T, C, O> CollectError<T, C, O> {
+    #[cold]
+    #[inline(never)]
+    fn unlikely_append(error: C) -> Self {
+        Self::AppendError(error)
+    }
+
     #[cold]
     #[inline(never)]
-    fn unlikely_collector(error: C) -> Self {
-        Self::Collector(error)
+    fn unlikely_consume(error: C) -> Self {
+        Self::ConsumeError(error)
+    }
+
+    #[inline(always)]
+    ///Returns collector error, regardless of whether it happened on append or consume
+    pub fn collector_error(&self) -> Option<&C> {
+        match self {
+            Self::AppendError(error) | Self::ConsumeError(error) => Some(error),
+            _ => None,
+        }
     }
 }
 
-impl<T: fmt::Display, C: fmt::Display> fmt::Display for CollectError<T, C> {
+impl<T: fmt::Display, C: fmt::Display, O> fmt::Display for CollectError<T, C, O> {
     #[inline(always)]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::Transport(error) => fmt::Display::fmt(error, fmt),
-            Self::Collector(error) => fmt::Display::fmt(error, fmt),
-            Self::Overflow => fmt.write_str("Overflow"),
+            Self::AppendError(error) => fmt::Display::fmt(error, fmt),
+            Self::ConsumeError(error) => fmt::Display::fmt(error, fmt),
+            Self::Overflow(_) => fmt.write_str("Overflow"),
+            Self::TooManyFrames => fmt.write_str("TooManyFrames"),
+            Self::Truncated { expected_min, actual } => fmt.write_fmt(format_args!("Truncated(expected at least {} bytes, got {})", expected_min, actual)),
+            Self::TooSlow { received, elapsed } => fmt.write_fmt(format_args!("TooSlow(received {} bytes in {:?})", received, elapsed)),
+            Self::LengthMismatch { expected, actual } => fmt.write_fmt(format_args!("LengthMismatch(expected {} bytes, got {})", expected, actual)),
+            Self::Timeout { received } => fmt.write_fmt(format_args!("Timeout(received {} bytes)", received)),
         }
     }
 }
 
 #[cfg(feature = "std")]
-impl<T: fmt::Display + fmt::Debug, E: fmt::Display + fmt::Debug> std::error::Error for CollectError<T, E> {}
+impl<T: std::error::Error + 'static, E: std::error::Error + 'static, O: fmt::Debug> std::error::Error for CollectError<T, E, O> {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Transport(error) => Some(error),
+            Self::AppendError(error) | Self::ConsumeError(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+type BoxError = std::boxed::Box<dyn std::error::Error + Send + Sync>;
+
+#[cfg(feature = "std")]
+#[inline]
+fn into_io_error(error: BoxError, kind: std::io::ErrorKind) -> std::io::Error {
+    match error.downcast::<std::io::Error>() {
+        Ok(error) => *error,
+        Err(error) => std::io::Error::new(kind, error),
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Into<BoxError>, C: Into<BoxError>, O> CollectError<T, C, O> {
+    ///Converts into `std::io::Error`, to be propagated within application error stack.
+    ///
+    ///Transport and collector errors become source of `std::io::Error` (or returned as it is, if they are `std::io::Error`),
+    ///with kind `Other` and `InvalidData` respectively.
+    ///Partial output of `Overflow` is dropped.
+    ///The rest are mapped to closest `std::io::ErrorKind`:
+    ///- `Overflow`, `TooManyFrames` and `LengthMismatch` - `InvalidData`
+    ///- `Truncated` - `UnexpectedEof`
+    ///- `TooSlow` and `Timeout` - `TimedOut`
+    pub fn into_io(self) -> std::io::Error {
+        use std::io::ErrorKind;
+        use core::convert::Infallible;
+
+        let (kind, error) = match self {
+            Self::Transport(error) => return into_io_error(error.into(), ErrorKind::Other),
+            Self::AppendError(error) | Self::ConsumeError(error) => return into_io_error(error.into(), ErrorKind::InvalidData),
+            Self::Overflow(_) => (ErrorKind::InvalidData, CollectError::<Infallible, Infallible>::Overflow(None)),
+            Self::TooManyFrames => (ErrorKind::InvalidData, CollectError::TooManyFrames),
+            Self::Truncated { expected_min, actual } => (ErrorKind::UnexpectedEof, CollectError::Truncated { expected_min, actual }),
+            Self::TooSlow { received, elapsed } => (ErrorKind::TimedOut, CollectError::TooSlow { received, elapsed }),
+            Self::LengthMismatch { expected, actual } => (ErrorKind::InvalidData, CollectError::LengthMismatch { expected, actual }),
+            Self::Timeout { received } => (ErrorKind::TimedOut, CollectError::Timeout { received }),
+        };
+        std::io::Error::new(kind, error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Into<BoxError>, C: Into<BoxError>, O> From<CollectError<T, C, O>> for std::io::Error {
+    #[inline(always)]
+    fn from(error: CollectError<T, C, O>) -> Self {
+        error.into_io()
+    }
+}
 
 ///`Collector` buffer
 pub trait Collector: Unpin {
@@ -133,6 +659,22 @@ pub trait Collector: Unpin {
     ///Returns size of collected so far.
     fn len(&self) -> usize;
 
+    #[inline(always)]
+    ///Returns whether nothing is collected so far.
+    ///
+    ///Default implementation checks `len` to be zero.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline(always)]
+    ///Hints collector that at least `additional` bytes are about to be appended.
+    ///
+    ///Default implementation does nothing.
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
     ///Callback to be called when header map is encountered.
     fn on_trailers(&mut self, headers: http::HeaderMap);
 
@@ -158,6 +700,11 @@ impl Collector for Vec<u8> {
         self.len()
     }
 
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional)
+    }
+
     #[inline(always)]
     fn on_trailers(&mut self, _: http::HeaderMap) {
     }
@@ -170,92 +717,669 @@ impl Collector for Vec<u8> {
     }
 }
 
-#[cfg(feature = "compress")]
+///Collects into caller owned buffer, leaving data in it.
+///
+///Note that existing content of buffer is included into `len`, hence it counts towards limit of `Collect`.
+impl Collector for &'_ mut Vec<u8> {
+    type Output = ();
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        Ok(())
+    }
+}
+
+///Collects into `BytesMut`, returning frozen `Bytes`.
+///
+///Uniquely owned frames are re-used without copying, when possible.
+impl Collector for bytes::BytesMut {
+    type Output = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        match data.try_into_mut() {
+            //Takes over buffer if empty, or joins if buffers are contiguous. Otherwise copies
+            Ok(data) => self.unsplit(data),
+            Err(data) => self.extend_from_slice(&data),
+        }
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        bytes::BytesMut::len(self)
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        bytes::BytesMut::reserve(self, additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        Ok(self.split().freeze())
+    }
+}
+
+///Collector that counts occurrences of single byte, discarding content.
+///
+///Its `len` reports number of bytes seen, so `Collect` limit is still applied.
+pub struct CountCollector {
+    needle: u8,
+    count: usize,
+    len: usize,
+}
+
+impl CountCollector {
+    #[inline(always)]
+    ///Creates new instance, counting occurrences of `needle`
+    pub const fn new(needle: u8) -> Self {
+        Self {
+            needle,
+            count: 0,
+            len: 0,
+        }
+    }
+}
+
+impl Collector for CountCollector {
+    type Output = usize;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.count = self.count.saturating_add(data.iter().filter(|byte| **byte == self.needle).count());
+        self.len = self.len.saturating_add(data.len());
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        Ok(mem::take(&mut self.count))
+    }
+}
+
+///Collector that discards content, counting total number of bytes.
+///
+///Useful to drain body (e.g. to reuse connection) without allocating buffer.
+///Its `len` reports number of bytes seen, so `Collect` limit is still applied.
+pub struct DiscardCollector {
+    len: usize,
+}
+
+impl DiscardCollector {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            len: 0,
+        }
+    }
+}
+
+impl Default for DiscardCollector {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for DiscardCollector {
+    type Output = usize;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.len = self.len.saturating_add(data.len());
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        Ok(mem::take(&mut self.len))
+    }
+}
+
+#[inline]
+///Removes `Content-Encoding` and `Content-Length` headers, to be used when body is fully decoded.
+pub fn strip_encoding_headers(headers: &mut http::HeaderMap) {
+    headers.remove(http::header::CONTENT_ENCODING);
+    headers.remove(http::header::CONTENT_LENGTH);
+}
+
+///Removes last `decoded` codings from `Content-Encoding`, to be used when body is partially decoded.
+///
+///As codings are listed in order of application, they are decoded starting from the last one.
+///Remaining codings are kept in single `Content-Encoding` header, which is removed if none remains.
+///
+///`Content-Length` is removed, unless `decoded` is zero, as it no longer matches body.
+pub fn strip_decoded_encodings(headers: &mut http::HeaderMap, decoded: usize) {
+    if decoded == 0 {
+        return;
+    }
+    headers.remove(http::header::CONTENT_LENGTH);
+
+    let mut codings = Vec::new();
+    for value in headers.get_all(http::header::CONTENT_ENCODING) {
+        if let Ok(value) = value.to_str() {
+            codings.extend(value.split(',').map(str::trim).filter(|coding| !coding.is_empty()).map(String::from));
+        }
+    }
+    headers.remove(http::header::CONTENT_ENCODING);
+
+    codings.truncate(codings.len().saturating_sub(decoded));
+    if !codings.is_empty() {
+        if let Ok(value) = http::HeaderValue::from_str(&codings.join(", ")) {
+            headers.insert(http::header::CONTENT_ENCODING, value);
+        }
+    }
+}
+
+#[cfg(any(feature = "compress", feature = "gzip"))]
 enum DecompressState {
     Uninit(Vec<u8>),
     Plain(Vec<u8>),
+    #[cfg(feature = "compress")]
     Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+    #[cfg(feature = "gzip")]
+    Inflate(inflate::InflateDecoder),
+    #[cfg(feature = "lz4")]
+    Lz4(lz4::Lz4Decoder),
 }
 
-#[cfg(feature = "compress")]
+#[cfg(any(feature = "compress", feature = "gzip"))]
 ///Smart body collector, that automatically de-compresses if it detects compression applied.
 ///
 ///Supported algorithms:
-///- `zstd`
+///- `zstd` (requires `compress` feature)
+///- `gzip` (requires `gzip` feature)
+///- `deflate` (requires `gzip` feature, only decoded via `with_encoding` or `from_content_encoding`)
+///- `lz4` frame format (requires `lz4` feature)
+///
+///Detection by magic number is best-effort: plain content, that happens to start with it, is treated as compressed.
+///Use `require` to enforce particular encoding.
+///
+///Trailers are ignored. To capture them (e.g. `grpc-status` of compressed `gRPC-web` response), wrap collector into `TrailersCollector`,
+///which outputs `(Vec<u8>, Option<http::HeaderMap>)` with all trailer frames merged.
 pub struct DecompressCollector {
     state: DecompressState,
+    required: Option<Encoding>,
+    limit: usize,
+    zstd_window_log_max: Option<u32>,
+    //Configuration error of already created decoder, reported on first append
+    error: Option<DecompressError>,
 }
 
-#[cfg(feature = "compress")]
+#[cfg(any(feature = "compress", feature = "gzip"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Content encoding, detected by `DecompressCollector`
+pub enum Encoding {
+    ///Content is not compressed
+    Identity,
+    #[cfg(feature = "compress")]
+    ///`zstd` compression
+    Zstd,
+    #[cfg(feature = "gzip")]
+    ///`gzip` compression
+    Gzip,
+    #[cfg(feature = "gzip")]
+    ///`deflate` compression, wrapped into `zlib` format.
+    ///
+    ///Cannot be detected, use `DecompressCollector::with_encoding` to decode it.
+    Deflate,
+    #[cfg(feature = "lz4")]
+    ///`lz4` frame compression
+    Lz4,
+}
+
+#[cfg(any(feature = "compress", feature = "gzip"))]
 impl DecompressCollector {
+    //Number of leading bytes required to detect encoding
+    const MAGIC_LEN: usize = 4;
+    #[cfg(feature = "compress")]
     const ZSTD_HEADER: [u8; 4] = 0xFD2FB528u32.to_le_bytes();
+    #[cfg(feature = "gzip")]
+    const GZIP_HEADER: [u8; 2] = [0x1f, 0x8b];
+    #[cfg(feature = "lz4")]
+    const LZ4_HEADER: [u8; 4] = 0x184D2204u32.to_le_bytes();
 
     #[inline(always)]
     ///Creates new instance
     pub const fn new() -> Self {
         Self {
-            state: DecompressState::Uninit(Vec::new())
+            state: DecompressState::Uninit(Vec::new()),
+            required: None,
+            limit: usize::MAX,
+            zstd_window_log_max: None,
+            error: None,
+        }
+    }
+
+    #[inline(always)]
+    ///Requires content to be of specified `encoding`.
+    ///
+    ///If detected encoding differs, `DecompressError::EncodingMismatch` is returned instead of silently passing content through.
+    ///Applies only to detection: encoding specified via `with_encoding` or `from_content_encoding` is decoded as it is.
+    pub const fn require(mut self, encoding: Encoding) -> Self {
+        self.required = Some(encoding);
+        self
+    }
+
+    #[inline]
+    ///Fails with `DecompressError::Overflow` once decompressed content exceeds `max_out` bytes.
+    ///
+    ///`Collect` limit applies to compressed content, hence this is required to protect against decompression bombs.
+    ///Limit is checked after every write into decoder, which for `zstd` produces at most single block, limiting overshoot.
+    ///`gzip`, `deflate` and `lz4` decoders check it while decoding, hence never produce more than `max_out` bytes.
+    ///
+    ///Can be combined with `with_encoding` or `from_content_encoding`.
+    pub fn with_limit(mut self, max_out: usize) -> Self {
+        self.limit = max_out;
+        match &mut self.state {
+            #[cfg(feature = "gzip")]
+            DecompressState::Inflate(decoder) => decoder.set_limit(max_out),
+            #[cfg(feature = "lz4")]
+            DecompressState::Lz4(decoder) => decoder.set_limit(max_out),
+            _ => (),
+        }
+        self
+    }
+
+    #[cfg(feature = "compress")]
+    #[inline(always)]
+    ///Allows `zstd` back-reference distance of up to `2^log_distance` bytes.
+    ///
+    ///Default decoder rejects content compressed with window larger than `2^27` bytes (e.g. using `--long` mode), which requires this to be decompressed.
+    ///Invalid value is reported as `DecompressError::Zstd` once `zstd` content is detected, or on first append if `zstd` is specified via `with_encoding`.
+    pub fn with_zstd_window_log_max(mut self, log_distance: u32) -> Self {
+        self.zstd_window_log_max = Some(log_distance);
+        if let DecompressState::Zstd(decoder) = &mut self.state {
+            if let Err(error) = decoder.window_log_max(log_distance) {
+                self.error = Some(DecompressError::Zstd(error));
+            }
+        }
+        self
+    }
+
+    #[cfg(feature = "compress")]
+    fn new_zstd(window_log_max: Option<u32>) -> Result<zstd::stream::write::Decoder<'static, Vec<u8>>, DecompressError> {
+        let mut decoder = zstd::stream::write::Decoder::new(Vec::new()).map_err(DecompressError::Zstd)?;
+        if let Some(log_distance) = window_log_max {
+            decoder.window_log_max(log_distance).map_err(DecompressError::Zstd)?;
+        }
+        Ok(decoder)
+    }
+
+    ///Creates new instance, with encoding specified by `Content-Encoding` header `value`.
+    ///
+    ///Recognized codings are `zstd`, `gzip` (or `x-gzip`), `deflate` and `identity`, which are decoded without sniffing content.
+    ///Hence plain content, that happens to start with compression magic number, is not mistaken for compressed one.
+    ///
+    ///Falls back to detecting encoding (same as `new`) when `value` is not recognized.
+    ///
+    ///Returns `DecompressError::UnsupportedEncoding` when `value` names known encoding, that cannot be decompressed,
+    ///or multiple codings are applied.
+    pub fn from_content_encoding(value: &http::HeaderValue) -> Result<Self, DecompressError> {
+        let value = match value.to_str() {
+            Ok(value) => value.trim(),
+            Err(_) => return Ok(Self::new()),
+        };
+        let unsupported = || Err(DecompressError::UnsupportedEncoding(value.into()));
+        if value.contains(',') {
+            return unsupported();
+        }
+
+        let encoding = if value.eq_ignore_ascii_case("identity") {
+            Encoding::Identity
+        } else if value.eq_ignore_ascii_case("zstd") {
+            #[cfg(feature = "compress")]
+            {
+                Encoding::Zstd
+            }
+            #[cfg(not(feature = "compress"))]
+            return unsupported();
+        } else if value.eq_ignore_ascii_case("gzip") || value.eq_ignore_ascii_case("x-gzip") {
+            #[cfg(feature = "gzip")]
+            {
+                Encoding::Gzip
+            }
+            #[cfg(not(feature = "gzip"))]
+            return unsupported();
+        } else if value.eq_ignore_ascii_case("deflate") {
+            #[cfg(feature = "gzip")]
+            {
+                Encoding::Deflate
+            }
+            #[cfg(not(feature = "gzip"))]
+            return unsupported();
+        } else if value.eq_ignore_ascii_case("br") || value.eq_ignore_ascii_case("compress") || value.eq_ignore_ascii_case("x-compress") {
+            return unsupported();
+        } else {
+            return Ok(Self::new());
+        };
+
+        Self::with_encoding(encoding)
+    }
+
+    ///Creates new instance, that decodes content as `encoding` without sniffing it.
+    ///
+    ///This is the only way to decode `Encoding::Deflate`, as it has no magic number to detect.
+    ///
+    ///Note that `Encoding::Deflate` is `zlib` format (RFC 1950), i.e. `DEFLATE` stream (RFC 1951) with 2 bytes header and `Adler-32` trailer,
+    ///as mandated for `Content-Encoding: deflate`. Raw `DEFLATE` stream, sent by some non-conforming servers, is rejected.
+    pub fn with_encoding(encoding: Encoding) -> Result<Self, DecompressError> {
+        let state = match encoding {
+            Encoding::Identity => DecompressState::Plain(Vec::new()),
+            #[cfg(feature = "compress")]
+            Encoding::Zstd => DecompressState::Zstd(Self::new_zstd(None)?),
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => DecompressState::Inflate(inflate::InflateDecoder::gzip()),
+            #[cfg(feature = "gzip")]
+            Encoding::Deflate => DecompressState::Inflate(inflate::InflateDecoder::zlib()),
+            #[cfg(feature = "lz4")]
+            Encoding::Lz4 => DecompressState::Lz4(lz4::Lz4Decoder::new()),
+        };
+
+        Ok(Self {
+            state,
+            required: None,
+            limit: usize::MAX,
+            zstd_window_log_max: None,
+            error: None,
+        })
+    }
+
+    #[inline(always)]
+    fn check_encoding(required: Option<Encoding>, actual: Encoding) -> Option<DecompressError> {
+        match required {
+            Some(expected) if expected != actual => Some(DecompressError::EncodingMismatch {
+                expected,
+                actual,
+            }),
+            _ => None,
+        }
+    }
+
+    ///Creates snapshot of buffered data and configuration, allowing to resume collection later via `restore`.
+    ///
+    ///Only possible while compression is not detected yet or content is detected as plain.
+    ///Streaming decoder state cannot be saved, hence `DecompressError::SnapshotUnsupported` is returned once decompression started.
+    pub fn snapshot(&self) -> Result<DecompressSnapshot, DecompressError> {
+        let content = match &self.state {
+            DecompressState::Uninit(buffer) => DecompressSnapshotContent::Undetected(buffer.clone()),
+            DecompressState::Plain(buffer) => DecompressSnapshotContent::Identity(buffer.clone()),
+            #[cfg(feature = "compress")]
+            DecompressState::Zstd(_) => return Err(DecompressError::SnapshotUnsupported),
+            #[cfg(feature = "gzip")]
+            DecompressState::Inflate(_) => return Err(DecompressError::SnapshotUnsupported),
+            #[cfg(feature = "lz4")]
+            DecompressState::Lz4(_) => return Err(DecompressError::SnapshotUnsupported),
+        };
+        Ok(DecompressSnapshot {
+            content,
+            required: self.required,
+            limit: self.limit,
+            zstd_window_log_max: self.zstd_window_log_max,
+        })
+    }
+
+    #[inline(always)]
+    ///Restores collector from previously created `snapshot`, including its configuration
+    pub fn restore(snapshot: DecompressSnapshot) -> Self {
+        let state = match snapshot.content {
+            DecompressSnapshotContent::Undetected(buffer) => DecompressState::Uninit(buffer),
+            DecompressSnapshotContent::Identity(buffer) => DecompressState::Plain(buffer),
+        };
+        Self {
+            state,
+            required: snapshot.required,
+            limit: snapshot.limit,
+            zstd_window_log_max: snapshot.zstd_window_log_max,
+            error: None,
         }
     }
 }
 
-#[cfg(feature = "compress")]
+#[cfg(any(feature = "compress", feature = "gzip"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+///Saved content of `DecompressCollector`
+pub enum DecompressSnapshotContent {
+    ///Encoding is not detected yet, with data buffered so far.
+    Undetected(Vec<u8>),
+    ///Content is detected as not compressed, with data collected so far.
+    Identity(Vec<u8>),
+}
+
+#[cfg(any(feature = "compress", feature = "gzip"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+///Saved state of `DecompressCollector`, including its configuration (required encoding, limit and `zstd` window)
+pub struct DecompressSnapshot {
+    content: DecompressSnapshotContent,
+    required: Option<Encoding>,
+    limit: usize,
+    zstd_window_log_max: Option<u32>,
+}
+
+#[cfg(any(feature = "compress", feature = "gzip"))]
+impl DecompressSnapshot {
+    #[inline(always)]
+    ///Access saved content
+    pub fn content(&self) -> &DecompressSnapshotContent {
+        &self.content
+    }
+
+    #[inline(always)]
+    ///Access buffered bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.content {
+            DecompressSnapshotContent::Undetected(buffer) => buffer,
+            DecompressSnapshotContent::Identity(buffer) => buffer,
+        }
+    }
+}
+
+#[cfg(any(feature = "compress", feature = "gzip"))]
 #[derive(Debug)]
 ///Decompression error
 pub enum DecompressError {
+    #[cfg(feature = "compress")]
     ///Zstd algorithm fail
-    Zstd(std::io::Error)
+    Zstd(std::io::Error),
+    ///Decompressed content exceeds limit
+    Overflow,
+    #[cfg(feature = "gzip")]
+    ///Gzip or deflate algorithm fail
+    Gzip(std::io::Error),
+    #[cfg(feature = "lz4")]
+    ///Lz4 algorithm fail
+    Lz4(std::io::Error),
+    ///Snapshot is requested after decompression started
+    SnapshotUnsupported,
+    ///`Content-Encoding` names encoding, that cannot be decompressed
+    UnsupportedEncoding(String),
+    ///Decompression is aborted via `DecompressProgressCollector`
+    Cancelled,
+    ///Detected encoding differs from required one
+    EncodingMismatch {
+        ///Required encoding
+        expected: Encoding,
+        ///Detected encoding
+        actual: Encoding,
+    },
 }
 
-#[cfg(feature = "compress")]
+#[cfg(any(feature = "compress", feature = "gzip"))]
 impl fmt::Display for DecompressError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            #[cfg(feature = "compress")]
             Self::Zstd(error) => fmt.write_fmt(format_args!("Zstd({})", error)),
+            Self::Overflow => fmt.write_str("Overflow"),
+            #[cfg(feature = "gzip")]
+            Self::Gzip(error) => fmt.write_fmt(format_args!("Gzip({})", error)),
+            #[cfg(feature = "lz4")]
+            Self::Lz4(error) => fmt.write_fmt(format_args!("Lz4({})", error)),
+            Self::SnapshotUnsupported => fmt.write_str("SnapshotUnsupported"),
+            Self::UnsupportedEncoding(encoding) => fmt.write_fmt(format_args!("UnsupportedEncoding({})", encoding)),
+            Self::Cancelled => fmt.write_str("Cancelled"),
+            Self::EncodingMismatch { expected, actual } => fmt.write_fmt(format_args!("EncodingMismatch(expected {:?}, got {:?})", expected, actual)),
         }
     }
 }
 
-#[cfg(feature = "compress")]
-impl Collector for DecompressCollector {
-    type Output = Vec<u8>;
-    type Error = DecompressError;
+#[cfg(any(feature = "compress", feature = "gzip"))]
+impl DecompressCollector {
+    #[cfg(feature = "compress")]
+    fn write_zstd(decoder: &mut zstd::stream::write::Decoder<'static, Vec<u8>>, mut data: &[u8], limit: usize) -> Option<DecompressError> {
+        use std::io::{self, Write};
 
-    #[inline(always)]
-    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
-        use std::io::Write;
+        //Every write produces limited amount of output, so check limit in between
+        while !data.is_empty() {
+            match decoder.write(data) {
+                Ok(0) => return Some(DecompressError::Zstd(io::ErrorKind::WriteZero.into())),
+                Ok(written) => data = &data[written..],
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Some(DecompressError::Zstd(error)),
+            }
+            if decoder.get_ref().len() > limit {
+                return Some(DecompressError::Overflow);
+            }
+        }
+        None
+    }
 
+    fn write(&mut self, data: bytes::Bytes) -> Option<DecompressError> {
         match &mut self.state {
             DecompressState::Uninit(ref mut buffer) => {
                 buffer.extend_from_slice(&data);
-                if buffer.len() < Self::ZSTD_HEADER.len() {
+                if buffer.len() < Self::MAGIC_LEN {
                     None
                 } else {
-                    if buffer.starts_with(&Self::ZSTD_HEADER) {
-                        match zstd::stream::write::Decoder::new(Vec::new()) {
-                            Ok(mut decoder) => match decoder.write_all(&buffer) {
-                                Ok(()) => {
-                                    self.state = DecompressState::Zstd(decoder);
-                                    None
-                                },
-                                Err(error) => Some(DecompressError::Zstd(error)),
+                    #[cfg(feature = "gzip")]
+                    if buffer.starts_with(&Self::GZIP_HEADER) {
+                        if let Some(error) = Self::check_encoding(self.required, Encoding::Gzip) {
+                            return Some(error);
+                        }
+                        let mut decoder = inflate::InflateDecoder::gzip();
+                        decoder.set_limit(self.limit);
+                        return match decoder.write_all(&buffer) {
+                            Ok(()) => {
+                                self.state = DecompressState::Inflate(decoder);
+                                None
                             },
-                            Err(error) => Some(DecompressError::Zstd(error)),
+                            Err(error) => Some(error),
+                        };
+                    }
+
+                    #[cfg(feature = "lz4")]
+                    if buffer.starts_with(&Self::LZ4_HEADER) {
+                        if let Some(error) = Self::check_encoding(self.required, Encoding::Lz4) {
+                            return Some(error);
                         }
-                    } else {
-                        self.state = DecompressState::Plain(mem::take(buffer));
-                        None
+                        let mut decoder = lz4::Lz4Decoder::new();
+                        decoder.set_limit(self.limit);
+                        return match decoder.write_all(&buffer) {
+                            Ok(()) => {
+                                self.state = DecompressState::Lz4(decoder);
+                                None
+                            },
+                            Err(error) => Some(error),
+                        };
                     }
+
+                    #[cfg(feature = "compress")]
+                    if buffer.starts_with(&Self::ZSTD_HEADER) {
+                        if let Some(error) = Self::check_encoding(self.required, Encoding::Zstd) {
+                            return Some(error);
+                        }
+                        return match Self::new_zstd(self.zstd_window_log_max) {
+                            Ok(mut decoder) => {
+                                let result = Self::write_zstd(&mut decoder, buffer, self.limit);
+                                self.state = DecompressState::Zstd(decoder);
+                                result
+                            },
+                            Err(error) => Some(error),
+                        };
+                    }
+
+                    if let Some(error) = Self::check_encoding(self.required, Encoding::Identity) {
+                        return Some(error);
+                    }
+                    self.state = DecompressState::Plain(mem::take(buffer));
+                    None
                 }
             },
             DecompressState::Plain(ref mut buffer) => {
                 buffer.extend_from_slice(&data);
                 None
             },
-            DecompressState::Zstd(ref mut decoder) => match decoder.write_all(&data) {
-                Ok(()) => None,
-                Err(error) => Some(DecompressError::Zstd(error)),
-            },
+            #[cfg(feature = "compress")]
+            DecompressState::Zstd(ref mut decoder) => Self::write_zstd(decoder, &data, self.limit),
+            #[cfg(feature = "gzip")]
+            DecompressState::Inflate(ref mut decoder) => decoder.write_all(&data).err(),
+            #[cfg(feature = "lz4")]
+            DecompressState::Lz4(ref mut decoder) => decoder.write_all(&data).err(),
+        }
+    }
+}
+
+#[cfg(any(feature = "compress", feature = "gzip"))]
+impl Collector for DecompressCollector {
+    type Output = Vec<u8>;
+    type Error = DecompressError;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        if let Some(error) = self.error.take() {
+            return Some(error);
+        }
+        match self.write(data) {
+            None if self.len() > self.limit => Some(DecompressError::Overflow),
+            result => result,
         }
     }
 
@@ -264,7 +1388,12 @@ impl Collector for DecompressCollector {
         match &self.state {
             DecompressState::Uninit(buffer) => buffer.len(),
             DecompressState::Plain(buffer) => buffer.len(),
+            #[cfg(feature = "compress")]
             DecompressState::Zstd(decoder) => decoder.get_ref().len(),
+            #[cfg(feature = "gzip")]
+            DecompressState::Inflate(decoder) => decoder.get_ref().len(),
+            #[cfg(feature = "lz4")]
+            DecompressState::Lz4(decoder) => decoder.get_ref().len(),
         }
     }
 
@@ -274,17 +1403,30 @@ impl Collector for DecompressCollector {
 
     #[inline(always)]
     fn consume(&mut self) -> Result<Self::Output, Self::Error> {
-        use std::io::Write;
-
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
         let mut result = DecompressState::Uninit(Vec::new());
         mem::swap(&mut result, &mut self.state);
-        match result {
-            DecompressState::Uninit(result) => Ok(result),
+        let result = match result {
+            DecompressState::Uninit(result) => match Self::check_encoding(self.required, Encoding::Identity) {
+                Some(error) => Err(error),
+                None => Ok(result),
+            },
             DecompressState::Plain(result) => Ok(result),
-            DecompressState::Zstd(mut decoder) => match decoder.flush() {
+            #[cfg(feature = "compress")]
+            DecompressState::Zstd(mut decoder) => match std::io::Write::flush(&mut decoder) {
                 Ok(()) => Ok(decoder.into_inner()),
                 Err(error) => Err(DecompressError::Zstd(error))
-            }
+            },
+            #[cfg(feature = "gzip")]
+            DecompressState::Inflate(decoder) => decoder.finish(),
+            #[cfg(feature = "lz4")]
+            DecompressState::Lz4(decoder) => decoder.finish(),
+        };
+        match result {
+            Ok(result) if result.len() > self.limit => Err(DecompressError::Overflow),
+            result => result,
         }
     }
 }
@@ -293,49 +1435,214 @@ impl Collector for DecompressCollector {
 ///
 ///## Arguments
 ///
-///- `T` - `HttpBody`
+///- `T` - `HttpBody`, which data is any `bytes::Buf`
 ///- `C` - Collector that implements `Collector` interface
-///- `S` - Size limit, when overflow happens, returns `Collect::Overflow` error
+///- `S` - Size limit, when overflow happens, returns `CollectError::Overflow` error
+///
+///Body is not required to be `Unpin`, in which case future must be pinned (e.g. via `core::pin::pin!`) before polling.
+///
+///Use `with_limit` when limit is known only at runtime.
 pub struct Collect<const S: usize, T, C> {
     body: T,
     collector: C,
+    received: u64,
+    frames: usize,
+    max_frames: usize,
+    //Lower bound is recorded on first poll, when requested
+    check_lower_bound: bool,
+    expected_min: Option<u64>,
+    expected_len: Option<u64>,
+    //Declared length exceeds limit
+    is_overflown: bool,
+    limit: usize,
 }
 
+pin_invariants!(impl[const S: usize, T, C] Collect<S, T, C> where T: Unpin);
+
+///Future that collects `HttpBody`, with size limit specified at runtime via `Collect::with_limit`
+pub type CollectDyn<T, C> = Collect<{ usize::MAX }, T, C>;
+
+///Maximum number of bytes preallocated by `Collect::with_headers` from `Content-Length`
+pub const PREALLOCATE_MAX: usize = 64 * 1024;
+
 impl<T, C, const S: usize> Collect<S, T, C> {
     ///Creates new instance
     pub fn new(body: T, collector: C) -> Self {
         Self {
             body,
             collector,
+            received: 0,
+            frames: 0,
+            max_frames: usize::MAX,
+            check_lower_bound: false,
+            expected_min: None,
+            expected_len: None,
+            is_overflown: false,
+            limit: S,
+        }
+    }
+
+    #[inline(always)]
+    ///Creates new instance with runtime size `limit`, which cannot exceed `S`.
+    ///
+    ///Overflow results in the same `CollectError::Overflow` error.
+    ///Use `CollectDyn` to avoid specifying `S`.
+    pub fn with_limit(body: T, collector: C, limit: usize) -> Self {
+        Self {
+            body,
+            collector,
+            received: 0,
+            frames: 0,
+            max_frames: usize::MAX,
+            check_lower_bound: false,
+            expected_min: None,
+            expected_len: None,
+            is_overflown: false,
+            limit: core::cmp::min(S, limit),
         }
     }
+
+    #[inline]
+    ///Creates new instance, preallocating collector using `Content-Length` from `headers`.
+    ///
+    ///Shortcut for `Collect::new(body, collector).with_headers(headers)`.
+    pub fn from_headers(headers: &http::HeaderMap, body: T, collector: C) -> Self where C: Collector {
+        Self::new(body, collector).with_headers(headers)
+    }
+
+    ///Preallocates collector using `Content-Length` from `headers`.
+    ///
+    ///If `Content-Length` exceeds limit, future resolves to `CollectError::Overflow` without polling body.
+    ///Invalid or absent `Content-Length` is ignored.
+    ///
+    ///As `Content-Length` is supplied by peer, preallocation is capped at `PREALLOCATE_MAX` bytes, and the rest is allocated as data arrives.
+    ///Call after `with_limit` to check `Content-Length` against runtime limit.
+    pub fn with_headers(mut self, headers: &http::HeaderMap) -> Self where C: Collector {
+        let content_length = headers.get(http::header::CONTENT_LENGTH)
+                                    .and_then(|value| value.to_str().ok())
+                                    .and_then(|value| value.trim().parse::<u64>().ok());
+        match content_length {
+            Some(len) if len > self.limit as u64 => self.is_overflown = true,
+            Some(len) => self.collector.reserve(core::cmp::min(len, PREALLOCATE_MAX as u64) as usize),
+            None => (),
+        }
+        self
+    }
+
+    #[inline(always)]
+    ///Enables check that body delivers at least `SizeHint::lower()` bytes.
+    ///
+    ///Lower bound is recorded on first poll, and if body ends short, returns `CollectError::Truncated` error.
+    pub fn expect_lower_bound(mut self) -> Self {
+        self.check_lower_bound = true;
+        self
+    }
+
+    #[inline(always)]
+    ///Enables check that body delivers exactly `len` bytes, returning `CollectError::LengthMismatch` otherwise.
+    ///
+    ///Intended as defensive check against framing bugs in transport (e.g. `HTTP/2` padding leaking into data), where `len` is declared `Content-Length`.
+    ///Excess is detected as soon as it is received, while shortage is detected at the end of body.
+    ///
+    ///Can be combined with `expect_lower_bound`, in which case both checks apply.
+    pub fn expect_exact_len(mut self, len: u64) -> Self {
+        self.expected_len = Some(len);
+        self
+    }
+
+    #[inline(always)]
+    ///Limits number of data frames to `max`, returning `CollectError::TooManyFrames` once body sends more.
+    ///
+    ///Protects against body trickling in many tiny frames, which byte limit does not catch.
+    ///Empty frames are skipped, hence not counted.
+    pub fn with_max_frames(mut self, max: usize) -> Self {
+        self.max_frames = max;
+        self
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes received from body so far
+    pub const fn received(&self) -> u64 {
+        self.received
+    }
+
+    #[inline(always)]
+    ///Returns number of data frames received from body so far.
+    ///
+    ///Empty frames are skipped, hence not counted.
+    ///Useful to diagnose whether body is sent as few large frames or many tiny ones.
+    pub const fn frames_seen(&self) -> usize {
+        self.frames
+    }
+
+    #[inline(always)]
+    ///Returns mutable reference to collector, e.g. to inspect data collected so far
+    pub fn collector_mut(&mut self) -> &mut C {
+        &mut self.collector
+    }
+
+    #[inline(always)]
+    ///Returns underlying body and collector.
+    ///
+    ///Once future resolved, body is drained and collector is already consumed, hence it is only left in state after `consume`.
+    ///This is still useful to re-use collector (e.g. to keep allocated buffer), or to inspect body.
+    pub fn into_parts(self) -> (T, C) {
+        (self.body, self.collector)
+    }
 }
 
-impl<E, T: HttpBody<Data = bytes::Bytes, Error = E> + Unpin, C: Collector, const S: usize> Future for Collect<S, T, C> {
-    type Output = Result<C::Output, CollectError<E, C::Error>>;
+impl<E, D: bytes::Buf, T: HttpBody<Data = D, Error = E>, C: Collector, const S: usize> Future for Collect<S, T, C> {
+    type Output = Result<C::Output, CollectError<E, C::Error, C::Output>>;
 
     fn poll(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
-        let this = self.get_mut();
+        project!(self => Collect { #[pin] body, collector, limit, check_lower_bound, expected_min, expected_len, is_overflown, received, frames, max_frames });
+        if *is_overflown {
+            return task::Poll::Ready(Err(CollectError::Overflow(None)));
+        } else if *check_lower_bound {
+            *check_lower_bound = false;
+            *expected_min = Some(body.size_hint().lower());
+        }
+
         loop {
-            let body = Pin::new(&mut this.body);
-            match HttpBody::poll_frame(body, ctx) {
+            match HttpBody::poll_frame(body.as_mut(), ctx) {
                 task::Poll::Ready(Some(frame)) => match frame {
                     Ok(frame) => match frame.into_data() {
-                        Ok(data) => match S.checked_sub(this.collector.len().saturating_add(data.len())) {
+                        Ok(mut data) => match limit.checked_sub(collector.len().saturating_add(data.remaining())) {
                             None => {
-                                break task::Poll::Ready(Err(CollectError::Overflow))
+                                //Fitting prefix is appended, so that partial output holds data up to the limit
+                                let len = limit.saturating_sub(collector.len());
+                                if len > 0 && *frames < *max_frames {
+                                    if let Some(error) = collector.append(data.copy_to_bytes(len)) {
+                                        break task::Poll::Ready(Err(CollectError::unlikely_append(error)));
+                                    }
+                                    *received = received.saturating_add(len as u64);
+                                    *frames = frames.saturating_add(1);
+                                }
+                                break task::Poll::Ready(Err(CollectError::Overflow(collector.consume().ok())))
                             }
-                            Some(_) => match data.len() {
+                            Some(_) => match data.remaining() {
                                 0 => continue,
-                                _ => match this.collector.append(data) {
-                                    Some(error) => break task::Poll::Ready(Err(CollectError::unlikely_collector(error))),
-                                    None => continue,
+                                _ if *frames >= *max_frames => break task::Poll::Ready(Err(CollectError::TooManyFrames)),
+                                //For `Bytes` this is zero-copy split, other buffers are copied into `Bytes`
+                                len => match collector.append(data.copy_to_bytes(len)) {
+                                    Some(error) => break task::Poll::Ready(Err(CollectError::unlikely_append(error))),
+                                    None => {
+                                        *received = received.saturating_add(len as u64);
+                                        *frames = frames.saturating_add(1);
+                                        match *expected_len {
+                                            Some(expected) if *received > expected => break task::Poll::Ready(Err(CollectError::LengthMismatch {
+                                                expected,
+                                                actual: *received,
+                                            })),
+                                            _ => continue,
+                                        }
+                                    },
                                 }
                             },
                         },
                         Err(frame) => match frame.into_trailers() {
                             Ok(headers) => {
-                                this.collector.on_trailers(headers);
+                                collector.on_trailers(headers);
                                 continue;
                             },
                             Err(_) => unreach!(),
@@ -343,9 +1650,23 @@ impl<E, T: HttpBody<Data = bytes::Bytes, Error = E> + Unpin, C: Collector, const
                     },
                     Err(error) => break task::Poll::Ready(Err(CollectError::Transport(error))),
                 },
-                task::Poll::Ready(None) => match this.collector.consume() {
-                    Ok(result) => break task::Poll::Ready(Ok(result)),
-                    Err(error) => break task::Poll::Ready(Err(CollectError::unlikely_collector(error))),
+                task::Poll::Ready(None) => match (*expected_min, *expected_len) {
+                    (Some(expected_min), _) if *received < expected_min => {
+                        break task::Poll::Ready(Err(CollectError::Truncated {
+                            expected_min,
+                            actual: *received,
+                        }))
+                    },
+                    (_, Some(expected)) if *received != expected => {
+                        break task::Poll::Ready(Err(CollectError::LengthMismatch {
+                            expected,
+                            actual: *received,
+                        }))
+                    },
+                    _ => match collector.consume() {
+                        Ok(result) => break task::Poll::Ready(Ok(result)),
+                        Err(error) => break task::Poll::Ready(Err(CollectError::unlikely_consume(error))),
+                    },
                 },
                 task::Poll::Pending => break task::Poll::Pending,
             };
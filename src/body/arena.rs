@@ -0,0 +1,184 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::{cmp, mem, ops};
+
+use super::Collector;
+
+///Byte storage made of fixed size blocks of `BLOCK` bytes, that are never reallocated.
+///
+///Once written, address of each byte stays the same until arena is dropped, making it suitable for parsers holding pointers into collected data.
+///Data is contiguous only within single block, use `get` to access it.
+pub struct ChunkedArena<const BLOCK: usize> {
+    blocks: Vec<Box<[u8; BLOCK]>>,
+    len: usize,
+}
+
+impl<const BLOCK: usize> ChunkedArena<BLOCK> {
+    #[inline(always)]
+    ///Creates new empty instance
+    pub const fn new() -> Self {
+        assert!(BLOCK > 0, "BLOCK cannot be zero");
+        Self {
+            blocks: Vec::new(),
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of stored bytes
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    ///Returns whether arena is empty
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    ///Returns byte at `idx`, if any
+    pub fn byte(&self, idx: usize) -> Option<u8> {
+        match idx < self.len {
+            true => Some(self.blocks[idx / BLOCK][idx % BLOCK]),
+            false => None,
+        }
+    }
+
+    ///Returns slice over `range`, if it is within stored data and does not cross block boundary.
+    pub fn get(&self, range: ops::Range<usize>) -> Option<&[u8]> {
+        if range.start > range.end || range.end > self.len {
+            return None;
+        }
+
+        let block = range.start / BLOCK;
+        let start = range.start % BLOCK;
+        let end = start + (range.end - range.start);
+        match end <= BLOCK {
+            true => self.blocks.get(block).map(|block| &block[start..end]).or(Some(&[])),
+            false => None,
+        }
+    }
+
+    #[inline]
+    ///Returns iterator over filled part of every block
+    pub fn blocks(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        let len = self.len;
+        self.blocks.iter().enumerate().map(move |(idx, block)| &block[..cmp::min(BLOCK, len - idx * BLOCK)])
+    }
+
+    ///Copies bytes starting at `start` into `out`, returning number of copied bytes.
+    ///
+    ///Allows to read range crossing block boundaries.
+    pub fn copy_to(&self, mut start: usize, out: &mut [u8]) -> usize {
+        let mut written = 0;
+        while written < out.len() && start < self.len {
+            let offset = start % BLOCK;
+            let size = cmp::min(cmp::min(BLOCK - offset, self.len - start), out.len() - written);
+            out[written..written + size].copy_from_slice(&self.blocks[start / BLOCK][offset..offset + size]);
+            written += size;
+            start += size;
+        }
+        written
+    }
+
+    ///Copies all stored data into contiguous buffer
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.len);
+        for block in self.blocks() {
+            result.extend_from_slice(block);
+        }
+        result
+    }
+
+    fn extend_from_slice(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let offset = self.len % BLOCK;
+            if offset == 0 && self.len / BLOCK == self.blocks.len() {
+                //Allocate on heap directly to avoid large array on stack
+                match vec![0u8; BLOCK].into_boxed_slice().try_into() {
+                    Ok(block) => self.blocks.push(block),
+                    Err(_) => unreach!(),
+                }
+            }
+
+            let size = cmp::min(BLOCK - offset, data.len());
+            let block = self.len / BLOCK;
+            self.blocks[block][offset..offset + size].copy_from_slice(&data[..size]);
+            self.len += size;
+            data = &data[size..];
+        }
+    }
+}
+
+impl<const BLOCK: usize> Default for ChunkedArena<BLOCK> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///Collector, that accumulates into `ChunkedArena`, never moving already collected data.
+///
+///Collected data can be accessed during collection via `arena`.
+pub struct ChunkedArenaCollector<const BLOCK: usize> {
+    arena: ChunkedArena<BLOCK>,
+}
+
+impl<const BLOCK: usize> ChunkedArenaCollector<BLOCK> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            arena: ChunkedArena::new(),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns data collected so far
+    pub const fn arena(&self) -> &ChunkedArena<BLOCK> {
+        &self.arena
+    }
+}
+
+impl<const BLOCK: usize> Default for ChunkedArenaCollector<BLOCK> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BLOCK: usize> Collector for ChunkedArenaCollector<BLOCK> {
+    type Output = ChunkedArena<BLOCK>;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.arena.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.arena.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        //Only pointers to blocks are reallocated, so it is fine
+        let blocks = self.arena.len.saturating_add(additional).div_ceil(BLOCK);
+        self.arena.blocks.reserve(blocks - self.arena.blocks.len())
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        Ok(mem::take(&mut self.arena))
+    }
+}
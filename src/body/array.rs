@@ -0,0 +1,96 @@
+use core::{fmt, mem};
+
+use super::Collector;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///`ArrayCollector` buffer is exhausted
+pub struct ArrayError {
+    capacity: usize,
+}
+
+impl ArrayError {
+    #[inline(always)]
+    ///Returns capacity of exhausted buffer
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl fmt::Display for ArrayError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_fmt(format_args!("Buffer of {} bytes is exhausted", self.capacity))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArrayError {}
+
+///Collector into caller provided fixed size buffer, requiring no heap allocation.
+///
+///Output is filled prefix of buffer.
+///Data frame that does not fit into remaining space is rejected with `ArrayError` as whole, leaving buffer with previously appended frames only.
+pub struct ArrayCollector<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> ArrayCollector<'a> {
+    #[inline(always)]
+    ///Creates new instance, writing into `buffer`
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self {
+            buffer,
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns total capacity of buffer
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    ///Returns data collected so far
+    pub fn filled(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+impl<'a> Collector for ArrayCollector<'a> {
+    type Output = &'a [u8];
+    type Error = ArrayError;
+
+    #[inline]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        match self.buffer.get_mut(self.len..self.len.saturating_add(data.len())) {
+            Some(dest) => {
+                dest.copy_from_slice(&data);
+                self.len += data.len();
+                None
+            },
+            None => Some(ArrayError {
+                capacity: self.buffer.len(),
+            }),
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        //Remaining space is kept, so that collector may continue into it
+        let (filled, rest) = mem::take(&mut self.buffer).split_at_mut(self.len);
+        self.buffer = rest;
+        self.len = 0;
+        Ok(filled)
+    }
+}
@@ -0,0 +1,88 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+
+///HTTP body over static data, such as embedded asset.
+///
+///Constructed without allocation or copy.
+#[derive(Clone, Copy, Debug)]
+pub struct AssetBody {
+    data: &'static [u8],
+}
+
+impl AssetBody {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(data: &'static [u8]) -> Self {
+        Self {
+            data,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns remaining content
+    pub const fn as_bytes(&self) -> &'static [u8] {
+        self.data
+    }
+}
+
+impl HttpBody for AssetBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn poll_frame(mut self: Pin<&mut Self>, _cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match self.data.is_empty() {
+            true => task::Poll::Ready(None),
+            false => {
+                let data = core::mem::take(&mut self.data);
+                task::Poll::Ready(Some(Ok(Frame::data(bytes::Bytes::from_static(data)))))
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.data.len() as u64)
+    }
+}
+
+///Static map of embedded assets, from path to content.
+///
+///Entries must be sorted by path, as lookup uses binary search.
+pub struct AssetMap {
+    entries: &'static [(&'static str, &'static [u8])],
+}
+
+impl AssetMap {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(entries: &'static [(&'static str, &'static [u8])]) -> Self {
+        Self {
+            entries,
+        }
+    }
+
+    #[inline]
+    ///Returns body of asset at `path`, if any
+    pub fn get(&self, path: &str) -> Option<AssetBody> {
+        debug_assert!(self.entries.windows(2).all(|pair| pair[0].0 < pair[1].0), "AssetMap entries must be sorted by path");
+
+        match self.entries.binary_search_by(|(entry, _)| (*entry).cmp(path)) {
+            Ok(idx) => Some(AssetBody::new(self.entries[idx].1)),
+            Err(_) => None,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns iterator over asset paths
+    pub fn paths(&self) -> impl Iterator<Item = &'static str> {
+        self.entries.iter().map(|(path, _)| *path)
+    }
+}
@@ -0,0 +1,51 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Collect, Collector, CollectError};
+
+///`Collect` wrapper, that invokes callback once, right before body is polled for the first time.
+///
+///Created by `Collect::before_first_frame`
+pub struct CollectBeforeFirstFrame<const S: usize, T, C, F> {
+    collect: Collect<S, T, C>,
+    callback: Option<F>,
+}
+
+pin_invariants!(impl[const S: usize, T, C, F] CollectBeforeFirstFrame<S, T, C, F> where Collect<S, T, C>: Unpin);
+
+impl<T, C, const S: usize> Collect<S, T, C> {
+    #[inline(always)]
+    ///Wraps future to call `callback` once before first frame is pulled from body.
+    ///
+    ///Intended for `Expect: 100-continue` flow, where server must signal client to start sending body
+    ///only once handler actually starts reading it.
+    pub fn before_first_frame<F: FnOnce()>(self, callback: F) -> CollectBeforeFirstFrame<S, T, C, F> {
+        CollectBeforeFirstFrame {
+            collect: self,
+            callback: Some(callback),
+        }
+    }
+}
+
+impl<T, C, F, const S: usize> CollectBeforeFirstFrame<S, T, C, F> {
+    #[inline(always)]
+    ///Returns underlying future
+    pub fn into_inner(self) -> Collect<S, T, C> {
+        self.collect
+    }
+}
+
+impl<E, D: bytes::Buf, T: HttpBody<Data = D, Error = E>, C: Collector, F: FnOnce(), const S: usize> Future for CollectBeforeFirstFrame<S, T, C, F> {
+    type Output = Result<C::Output, CollectError<E, C::Error, C::Output>>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        project!(self => CollectBeforeFirstFrame { #[pin] collect, callback });
+
+        if let Some(callback) = callback.take() {
+            callback();
+        }
+        Future::poll(collect, ctx)
+    }
+}
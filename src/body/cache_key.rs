@@ -0,0 +1,85 @@
+use core::cell::OnceCell;
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Body, Frame, SizeHint};
+
+//FNV-1a 64
+const fn fnv1a(data: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    let mut idx = 0;
+    while idx < data.len() {
+        hash ^= data[idx] as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        idx += 1;
+    }
+    hash
+}
+
+impl Body {
+    #[inline(always)]
+    ///Computes fast non-cryptographic hash (`FNV-1a`) of remaining content, to be used as cache key.
+    ///
+    ///Hash is recomputed on every call, use `CacheKeyBody` to memoize it.
+    pub fn cache_key(&self) -> u64 {
+        fnv1a(&self.inner)
+    }
+}
+
+///`Body` wrapper, that memoizes its cache key.
+///
+///Key is computed on first call to `cache_key` over remaining content, hence it should be called before body is polled.
+pub struct CacheKeyBody {
+    body: Body,
+    key: OnceCell<u64>,
+}
+
+impl CacheKeyBody {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(body: Body) -> Self {
+        Self {
+            body,
+            key: OnceCell::new(),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns cache key, computing it only once
+    pub fn cache_key(&self) -> u64 {
+        *self.key.get_or_init(|| self.body.cache_key())
+    }
+
+    #[inline(always)]
+    ///Returns underlying body
+    pub fn into_inner(self) -> Body {
+        self.body
+    }
+}
+
+impl From<Body> for CacheKeyBody {
+    #[inline(always)]
+    fn from(body: Body) -> Self {
+        Self::new(body)
+    }
+}
+
+impl HttpBody for CacheKeyBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        HttpBody::poll_frame(Pin::new(&mut self.body), cx)
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
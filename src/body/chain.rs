@@ -0,0 +1,109 @@
+use core::pin::Pin;
+use core::{fmt, task};
+
+use super::{HttpBody, Frame, SizeHint};
+
+#[derive(Debug)]
+///`ChainBody` error
+pub enum ChainError<A, B> {
+    ///First body error
+    First(A),
+    ///Second body error
+    Second(B),
+}
+
+impl<A: fmt::Display, B: fmt::Display> fmt::Display for ChainError<A, B> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::First(error) => fmt::Display::fmt(error, fmt),
+            Self::Second(error) => fmt::Display::fmt(error, fmt),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<A: fmt::Display + fmt::Debug, B: fmt::Display + fmt::Debug> std::error::Error for ChainError<A, B> {}
+
+pin_project_lite::pin_project! {
+    ///HTTP body, that emits body `A` to completion and then body `B`, similarly to `std::io::Read::chain`.
+    ///
+    ///Useful to prepend small preamble to streamed body without buffering it.
+    ///
+    ///As trailers may only end body, trailers of `A` are dropped, while trailers of `B` are passed as it is.
+    #[project = ChainBodyProj]
+    pub struct ChainBody<A, B> {
+        #[pin]
+        first: A,
+        #[pin]
+        second: B,
+        is_first_done: bool,
+    }
+}
+
+impl<A, B> ChainBody<A, B> {
+    #[inline(always)]
+    ///Creates new instance, emitting `first` followed by `second`
+    pub const fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            is_first_done: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns underlying bodies
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: HttpBody, B: HttpBody<Data = A::Data>> HttpBody for ChainBody<A, B> {
+    type Data = A::Data;
+    type Error = ChainError<A::Error, B::Error>;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let ChainBodyProj { mut first, second, is_first_done } = self.project();
+
+        while !*is_first_done {
+            match HttpBody::poll_frame(first.as_mut(), cx) {
+                task::Poll::Pending => return task::Poll::Pending,
+                task::Poll::Ready(Some(Ok(frame))) => if frame.is_data() {
+                    return task::Poll::Ready(Some(Ok(frame)));
+                },
+                task::Poll::Ready(Some(Err(error))) => return task::Poll::Ready(Some(Err(ChainError::First(error)))),
+                task::Poll::Ready(None) => *is_first_done = true,
+            }
+        }
+
+        match HttpBody::poll_frame(second, cx) {
+            task::Poll::Ready(Some(Err(error))) => task::Poll::Ready(Some(Err(ChainError::Second(error)))),
+            task::Poll::Ready(Some(Ok(frame))) => task::Poll::Ready(Some(Ok(frame))),
+            task::Poll::Ready(None) => task::Poll::Ready(None),
+            task::Poll::Pending => task::Poll::Pending,
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        (self.is_first_done || self.first.is_end_stream()) && self.second.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let second = self.second.size_hint();
+        if self.is_first_done {
+            return second;
+        }
+
+        let first = self.first.size_hint();
+        match (first.exact(), second.exact()) {
+            (Some(first), Some(second)) => SizeHint::with_exact(first.saturating_add(second)),
+            _ => {
+                let mut hint = SizeHint::new();
+                hint.set_lower(first.lower().saturating_add(second.lower()));
+                hint
+            },
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//!Single consumer channel with optional capacity, used to feed streaming bodies
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::task;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+struct Shared<T> {
+    queue: VecDeque<T>,
+    capacity: usize,
+    senders: usize,
+    is_receiver_alive: bool,
+    waker: Option<task::Waker>,
+    //Senders waiting for free capacity
+    send_wakers: Vec<task::Waker>,
+}
+
+struct Channel<T> {
+    shared: Mutex<Shared<T>>,
+}
+
+impl<T> Channel<T> {
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, Shared<T>> {
+        //State is always consistent as there is no panicking code under lock
+        self.shared.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+pub(crate) struct Sender<T> {
+    channel: Arc<Channel<T>>,
+}
+
+pub(crate) struct Receiver<T> {
+    channel: Arc<Channel<T>>,
+}
+
+#[inline(always)]
+pub(crate) fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    bounded(usize::MAX)
+}
+
+///Creates channel, that holds at most `capacity` values, which must not be zero
+pub(crate) fn bounded<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    debug_assert_ne!(capacity, 0);
+    let channel = Arc::new(Channel {
+        shared: Mutex::new(Shared {
+            queue: VecDeque::new(),
+            capacity,
+            senders: 1,
+            is_receiver_alive: true,
+            waker: None,
+            send_wakers: Vec::new(),
+        })
+    });
+
+    (Sender { channel: channel.clone() }, Receiver { channel })
+}
+
+impl<T> Sender<T> {
+    fn push(mut shared: MutexGuard<'_, Shared<T>>, value: T) {
+        shared.queue.push_back(value);
+        let waker = shared.waker.take();
+        drop(shared);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    ///Enqueues `value`, returning it back if receiver is gone or channel is full
+    pub(crate) fn send(&self, value: T) -> Result<(), T> {
+        let shared = self.channel.lock();
+        if !shared.is_receiver_alive || shared.queue.len() >= shared.capacity {
+            return Err(value);
+        }
+
+        Self::push(shared, value);
+        Ok(())
+    }
+
+    ///Enqueues `value` once there is capacity, returning it back if receiver is gone.
+    ///
+    ///`value` is taken only when `Ready` is returned
+    pub(crate) fn poll_send(&self, ctx: &mut task::Context<'_>, value: &mut Option<T>) -> task::Poll<Result<(), T>> {
+        let mut shared = self.channel.lock();
+        let item = match value.take() {
+            Some(item) => item,
+            None => return task::Poll::Ready(Ok(())),
+        };
+        if !shared.is_receiver_alive {
+            return task::Poll::Ready(Err(item));
+        } else if shared.queue.len() >= shared.capacity {
+            *value = Some(item);
+            if !shared.send_wakers.iter().any(|waker| waker.will_wake(ctx.waker())) {
+                shared.send_wakers.push(ctx.waker().clone());
+            }
+            return task::Poll::Pending;
+        }
+
+        Self::push(shared, item);
+        task::Poll::Ready(Ok(()))
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_closed(&self) -> bool {
+        !self.channel.lock().is_receiver_alive
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        self.channel.lock().senders += 1;
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut shared = self.channel.lock();
+        shared.senders -= 1;
+        let waker = match shared.senders {
+            0 => shared.waker.take(),
+            _ => None,
+        };
+        drop(shared);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    ///Polls for next value, returning `None` once all senders are gone and queue is drained
+    pub(crate) fn poll_recv(&mut self, ctx: &mut task::Context<'_>) -> task::Poll<Option<T>> {
+        let mut shared = self.channel.lock();
+        match shared.queue.pop_front() {
+            Some(value) => {
+                let send_wakers = core::mem::take(&mut shared.send_wakers);
+                drop(shared);
+
+                for waker in send_wakers {
+                    waker.wake();
+                }
+                task::Poll::Ready(Some(value))
+            },
+            None => match shared.senders {
+                0 => task::Poll::Ready(None),
+                _ => {
+                    match shared.waker {
+                        Some(ref waker) if waker.will_wake(ctx.waker()) => (),
+                        _ => shared.waker = Some(ctx.waker().clone()),
+                    }
+                    task::Poll::Pending
+                }
+            }
+        }
+    }
+
+    #[inline(always)]
+    ///Returns whether all senders are gone and queue is drained
+    pub(crate) fn is_terminated(&self) -> bool {
+        let shared = self.channel.lock();
+        shared.senders == 0 && shared.queue.is_empty()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut shared = self.channel.lock();
+        shared.is_receiver_alive = false;
+        let queue = core::mem::take(&mut shared.queue);
+        let send_wakers = core::mem::take(&mut shared.send_wakers);
+        drop(shared);
+
+        //Values are dropped outside of lock, as their drop may panic
+        drop(queue);
+        for waker in send_wakers {
+            waker.wake();
+        }
+    }
+}
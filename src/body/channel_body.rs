@@ -0,0 +1,95 @@
+use core::pin::Pin;
+use core::{task, fmt};
+
+use super::{HttpBody, Frame};
+use super::channel::{self, Sender, Receiver};
+
+///Sender of data into paired `ChannelBody`
+///
+///Once all senders are dropped, body ends.
+pub struct ChannelSender<E> {
+    sender: Sender<Result<bytes::Bytes, E>>,
+}
+
+impl<E> ChannelSender<E> {
+    #[inline(always)]
+    ///Sends `item`, which is emitted as data frame or error by body.
+    ///
+    ///Returns `item` back if `ChannelBody` is dropped, or if body is created via `ChannelBody::bounded` and its capacity is exhausted.
+    pub fn send(&self, item: Result<bytes::Bytes, E>) -> Result<(), Result<bytes::Bytes, E>> {
+        self.sender.send(item)
+    }
+
+    ///Sends `item`, waiting until body has capacity for it.
+    ///
+    ///Returns `item` back if `ChannelBody` is dropped.
+    pub async fn send_async(&self, item: Result<bytes::Bytes, E>) -> Result<(), Result<bytes::Bytes, E>> {
+        let mut item = Some(item);
+        core::future::poll_fn(|ctx| self.sender.poll_send(ctx, &mut item)).await
+    }
+
+    #[inline(always)]
+    ///Returns whether `ChannelBody` is dropped
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+impl<E> Clone for ChannelSender<E> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+///Streaming body, emitting data produced incrementally via paired `ChannelSender`, which can be moved into another task.
+///
+///Size of body is unknown, and body ends once all senders are dropped.
+///
+///Channel created via `channel` is unbounded: if producer is faster than client, all pending data is buffered in memory.
+///Use `bounded` to apply backpressure via `ChannelSender::send_async`.
+pub struct ChannelBody<E> {
+    receiver: Receiver<Result<bytes::Bytes, E>>,
+}
+
+impl<E> ChannelBody<E> {
+    #[inline]
+    ///Creates new body with paired sender
+    pub fn channel() -> (ChannelSender<E>, Self) {
+        let (sender, receiver) = channel::channel();
+        (ChannelSender { sender }, Self { receiver })
+    }
+
+    #[inline]
+    ///Creates new body with paired sender, buffering at most `capacity` items (at least 1).
+    ///
+    ///Once capacity is exhausted, `ChannelSender::send` fails, while `ChannelSender::send_async` waits until body is polled.
+    pub fn bounded(capacity: usize) -> (ChannelSender<E>, Self) {
+        let (sender, receiver) = channel::bounded(core::cmp::max(capacity, 1));
+        (ChannelSender { sender }, Self { receiver })
+    }
+}
+
+impl<E> HttpBody for ChannelBody<E> {
+    type Data = bytes::Bytes;
+    type Error = E;
+
+    #[inline(always)]
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.receiver.poll_recv(cx).map(|item| item.map(|item| item.map(Frame::data)))
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.receiver.is_terminated()
+    }
+}
+
+impl<E> fmt::Debug for ChannelBody<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("ChannelBody")
+    }
+}
@@ -0,0 +1,192 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+use super::Collector;
+
+///Text encoding
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Charset {
+    ///`UTF-8`
+    Utf8,
+    ///`ISO-8859-1`, each byte maps to code point of the same value
+    Latin1,
+    ///`UTF-16` little-endian
+    Utf16Le,
+    ///`UTF-16` big-endian
+    Utf16Be,
+}
+
+///Extracts value of `charset` parameter from `Content-Type` header value.
+///
+///Surrounding quotes are removed, if present.
+pub fn content_type_charset(value: &http::HeaderValue) -> Option<&str> {
+    let value = value.to_str().ok()?;
+
+    //Skip media type
+    for param in value.split(';').skip(1) {
+        let (name, value) = match param.split_once('=') {
+            Some(param) => param,
+            None => continue,
+        };
+
+        if name.trim().eq_ignore_ascii_case("charset") {
+            let value = value.trim();
+            return match value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+                Some(value) => Some(value),
+                None => Some(value),
+            }
+        }
+    }
+
+    None
+}
+
+impl Charset {
+    ///Selects charset by its label (case insensitive), returning `None` if it is not supported.
+    ///
+    ///`utf-16` without explicit endianness is treated as big-endian, unless BOM says otherwise.
+    pub fn from_label(label: &str) -> Option<Self> {
+        const UTF8: &[&str] = &["utf-8", "utf8", "unicode-1-1-utf-8"];
+        const LATIN1: &[&str] = &["iso-8859-1", "iso8859-1", "iso_8859-1", "latin1", "l1", "us-ascii", "ascii"];
+        const UTF16LE: &[&str] = &["utf-16le"];
+        const UTF16BE: &[&str] = &["utf-16be", "utf-16"];
+
+        let label = label.trim();
+        let is_label = |labels: &[&str]| labels.iter().any(|expected| expected.eq_ignore_ascii_case(label));
+
+        if is_label(UTF8) {
+            Some(Self::Utf8)
+        } else if is_label(LATIN1) {
+            Some(Self::Latin1)
+        } else if is_label(UTF16LE) {
+            Some(Self::Utf16Le)
+        } else if is_label(UTF16BE) {
+            Some(Self::Utf16Be)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    ///Selects charset from `charset` parameter of `Content-Type` header value.
+    ///
+    ///Returns `None` if parameter is absent or charset is not supported.
+    pub fn from_content_type(value: &http::HeaderValue) -> Option<Self> {
+        content_type_charset(value).and_then(Self::from_label)
+    }
+
+    ///Decodes `bytes` into `String`.
+    ///
+    ///For `UTF-16` leading BOM is skipped and overrides endianness.
+    pub fn decode(self, bytes: Vec<u8>) -> Result<String, CharsetError> {
+        match self {
+            Self::Utf8 => String::from_utf8(bytes).map_err(|error| CharsetError::Utf8(error.utf8_error())),
+            Self::Latin1 => Ok(bytes.iter().map(|byte| *byte as char).collect()),
+            Self::Utf16Le | Self::Utf16Be => {
+                let (bytes, is_le) = match bytes.get(..2) {
+                    Some([0xFF, 0xFE]) => (&bytes[2..], true),
+                    Some([0xFE, 0xFF]) => (&bytes[2..], false),
+                    _ => (&bytes[..], self == Self::Utf16Le),
+                };
+
+                if bytes.len() % 2 != 0 {
+                    return Err(CharsetError::Utf16);
+                }
+
+                let units = bytes.chunks_exact(2).map(|unit| match is_le {
+                    true => u16::from_le_bytes([unit[0], unit[1]]),
+                    false => u16::from_be_bytes([unit[0], unit[1]]),
+                });
+                char::decode_utf16(units).collect::<Result<String, _>>().map_err(|_| CharsetError::Utf16)
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+///Text decoding error
+pub enum CharsetError {
+    ///Invalid `UTF-8` sequence
+    Utf8(core::str::Utf8Error),
+    ///Invalid `UTF-16` sequence
+    Utf16,
+}
+
+impl fmt::Display for CharsetError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Utf8(error) => fmt.write_fmt(format_args!("Utf8({})", error)),
+            Self::Utf16 => fmt.write_str("Utf16"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CharsetError {}
+
+///Collector of text, decoding it according to `Charset` once body is complete
+pub struct TextCollector {
+    charset: Charset,
+    buffer: Vec<u8>,
+}
+
+impl TextCollector {
+    #[inline(always)]
+    ///Creates new instance with specified `charset`
+    pub const fn new(charset: Charset) -> Self {
+        Self {
+            charset,
+            buffer: Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Creates new instance with charset specified by `Content-Type` header value.
+    ///
+    ///Falls back to `UTF-8` if charset is absent, while returning `None` if declared charset is not supported.
+    pub fn from_content_type(value: &http::HeaderValue) -> Option<Self> {
+        match content_type_charset(value) {
+            Some(label) => Charset::from_label(label).map(Self::new),
+            None => Some(Self::new(Charset::Utf8)),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns charset used for decoding
+    pub const fn charset(&self) -> Charset {
+        self.charset
+    }
+}
+
+impl Collector for TextCollector {
+    type Output = String;
+    type Error = CharsetError;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.buffer.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        self.charset.decode(mem::take(&mut self.buffer))
+    }
+}
@@ -0,0 +1,124 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::mem;
+
+use super::Collector;
+use crate::hash::{Digest, Sha256};
+
+const WINDOW: usize = 48;
+
+const fn buzhash_table() -> [u32; 256] {
+    //splitmix64, to get fixed pseudo random table
+    let mut table = [0u32; 256];
+    let mut state = 0x9e3779b97f4a7c15u64;
+    let mut idx = 0;
+    while idx < table.len() {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut value = state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^= value >> 31;
+        table[idx] = value as u32;
+        idx += 1;
+    }
+    table
+}
+
+static BUZHASH: [u32; 256] = buzhash_table();
+
+///Collector, that splits body into content-defined chunks, suitable for deduplication.
+///
+///Boundaries are determined by buzhash rolling hash over 48 bytes window, so they depend only on content, and not on frame boundaries or offset.
+///Hence, inserting data into body changes only chunks around insertion.
+///
+///Output is list of chunks with their `SHA-256` digests.
+pub struct ChunkingCollector {
+    min: usize,
+    max: usize,
+    mask: u32,
+    hash: u32,
+    window: [u8; WINDOW],
+    current: bytes::BytesMut,
+    hasher: Sha256,
+    chunks: Vec<(bytes::Bytes, [u8; 32])>,
+    len: usize,
+}
+
+impl ChunkingCollector {
+    ///Creates new instance with chunk size in range `min..=max`, averaging `avg`, which is rounded to power of two.
+    ///
+    ///Panics if `min` is zero or `max` is less than `min`.
+    pub fn new(min: usize, avg: usize, max: usize) -> Self {
+        assert!(min > 0, "min cannot be zero");
+        assert!(max >= min, "max cannot be less than min");
+        Self {
+            min,
+            max,
+            mask: (avg.max(1).next_power_of_two() - 1) as u32,
+            hash: 0,
+            window: [0; WINDOW],
+            current: bytes::BytesMut::new(),
+            hasher: Sha256::new(),
+            chunks: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn cut(&mut self) {
+        let data = self.current.split().freeze();
+        let digest = mem::take(&mut self.hasher).finalize();
+        self.chunks.push((data, digest.into()));
+        self.hash = 0;
+    }
+}
+
+impl Collector for ChunkingCollector {
+    type Output = Vec<(bytes::Bytes, [u8; 32])>;
+    type Error = core::convert::Infallible;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.len += data.len();
+
+        let mut start = 0;
+        for (idx, byte) in data.iter().enumerate() {
+            let chunk_len = self.current.len() + idx - start + 1;
+            let pos = chunk_len % WINDOW;
+            let out = mem::replace(&mut self.window[pos], *byte);
+
+            self.hash = self.hash.rotate_left(1) ^ BUZHASH[*byte as usize];
+            if chunk_len > WINDOW {
+                self.hash ^= BUZHASH[out as usize].rotate_left(WINDOW as u32);
+            }
+
+            if chunk_len >= self.max || (chunk_len >= self.min && self.hash & self.mask == 0) {
+                let chunk = &data[start..=idx];
+                self.current.extend_from_slice(chunk);
+                self.hasher.update(chunk);
+                self.cut();
+                start = idx + 1;
+            }
+        }
+
+        let rest = &data[start..];
+        self.current.extend_from_slice(rest);
+        self.hasher.update(rest);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        if !self.current.is_empty() {
+            self.cut();
+        }
+        Ok(mem::take(&mut self.chunks))
+    }
+}
@@ -0,0 +1,95 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::{cmp, mem, task};
+
+use super::{HttpBody, Collect, Collector, CollectError};
+
+type CollectResult<T, C> = Result<<C as Collector>::Output, CollectError<<T as HttpBody>::Error, <C as Collector>::Error, <C as Collector>::Output>>;
+type PinnedCollect<const S: usize, T, C> = Pin<Box<Collect<S, T, C>>>;
+
+///Future that collects multiple bodies, with at most `concurrency` bodies being collected at the same time.
+///
+///Each body is collected with its own collector, created by factory, and is limited to `S` bytes.
+///Results are returned in the same order as bodies, regardless of completion order.
+///
+///All bodies in progress share the same waker, hence every wake up polls all of them.
+pub struct CollectAll<const S: usize, T: HttpBody, C: Collector, I, F> {
+    bodies: I,
+    factory: F,
+    concurrency: usize,
+    active: Vec<(usize, PinnedCollect<S, T, C>)>,
+    results: Vec<Option<CollectResult<T, C>>>,
+}
+
+impl<const S: usize, T: HttpBody, C: Collector, I: Iterator<Item = T>, F: FnMut() -> C> CollectAll<S, T, C, I, F> {
+    ///Creates new instance, collecting `bodies` using collectors created by `factory`.
+    ///
+    ///`concurrency` of 0 is treated as 1.
+    pub fn new<B: IntoIterator<IntoIter = I>>(bodies: B, factory: F, concurrency: usize) -> Self {
+        let bodies = bodies.into_iter();
+        let concurrency = cmp::max(concurrency, 1);
+        Self {
+            results: Vec::with_capacity(bodies.size_hint().0),
+            active: Vec::with_capacity(cmp::min(concurrency, bodies.size_hint().0)),
+            bodies,
+            factory,
+            concurrency,
+        }
+    }
+
+    fn fill(&mut self) {
+        while self.active.len() < self.concurrency {
+            match self.bodies.next() {
+                Some(body) => {
+                    self.active.push((self.results.len(), Box::pin(Collect::new(body, (self.factory)()))));
+                    self.results.push(None);
+                },
+                None => break,
+            }
+        }
+    }
+}
+
+//Fields are never pinned, futures in progress are pinned on heap
+impl<const S: usize, T: HttpBody, C: Collector, I, F> Unpin for CollectAll<S, T, C, I, F> {}
+
+impl<const S: usize, D: bytes::Buf, T: HttpBody<Data = D>, C: Collector, I: Iterator<Item = T>, F: FnMut() -> C> Future for CollectAll<S, T, C, I, F> {
+    type Output = Vec<CollectResult<T, C>>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            this.fill();
+            if this.active.is_empty() {
+                let results = mem::take(&mut this.results);
+                break task::Poll::Ready(results.into_iter().map(|result| match result {
+                    Some(result) => result,
+                    None => unreach!(),
+                }).collect());
+            }
+
+            let mut is_progress = false;
+            let mut idx = 0;
+            while idx < this.active.len() {
+                let (result_idx, collect) = &mut this.active[idx];
+                match Future::poll(collect.as_mut(), ctx) {
+                    task::Poll::Ready(result) => {
+                        this.results[*result_idx] = Some(result);
+                        this.active.swap_remove(idx);
+                        is_progress = true;
+                    },
+                    task::Poll::Pending => idx += 1,
+                }
+            }
+
+            if !is_progress {
+                break task::Poll::Pending;
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::{fmt, mem, task};
+use std::io::{self, Write};
+
+use super::{HttpBody, Frame};
+
+#[derive(Debug)]
+///`CompressBody` error
+pub enum CompressError<E> {
+    ///Underlying body error
+    Inner(E),
+    ///Zstd algorithm fail
+    Zstd(io::Error),
+}
+
+impl<E: fmt::Display> fmt::Display for CompressError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inner(error) => fmt::Display::fmt(error, fmt),
+            Self::Zstd(error) => fmt.write_fmt(format_args!("Zstd({})", error)),
+        }
+    }
+}
+
+impl<E: fmt::Display + fmt::Debug> std::error::Error for CompressError<E> {}
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that compresses underlying body using `zstd`.
+    ///
+    ///Every data frame is fed into encoder, and compressed output, if any, is emitted as frame.
+    ///Once underlying body ends, encoder is finished and remaining output is emitted as final frame, followed by trailers, if any.
+    ///
+    ///Size of compressed body is unknown, hence `Content-Length` must not be set,
+    ///while `Content-Encoding: zstd` should be set by user.
+    #[project = CompressBodyProj]
+    pub struct CompressBody<T> {
+        #[pin]
+        body: T,
+        encoder: Option<zstd::stream::write::Encoder<'static, Vec<u8>>>,
+        trailers: Option<http::HeaderMap>,
+    }
+}
+
+impl<T> CompressBody<T> {
+    #[inline]
+    ///Creates new instance, compressing `body` with specified `level`.
+    ///
+    ///`0` selects default level.
+    pub fn new(body: T, level: i32) -> io::Result<Self> {
+        Ok(Self {
+            body,
+            encoder: Some(zstd::stream::write::Encoder::new(Vec::new(), level)?),
+            trailers: None,
+        })
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>> HttpBody for CompressBody<T> {
+    type Data = bytes::Bytes;
+    type Error = CompressError<T::Error>;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let CompressBodyProj { mut body, encoder, trailers } = self.project();
+
+        loop {
+            let writer = match encoder.as_mut() {
+                Some(writer) => writer,
+                None => return task::Poll::Ready(trailers.take().map(|trailers| Ok(Frame::trailers(trailers)))),
+            };
+
+            let is_end = match HttpBody::poll_frame(body.as_mut(), cx) {
+                task::Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                    Ok(data) => {
+                        if let Err(error) = writer.write_all(&data) {
+                            return task::Poll::Ready(Some(Err(CompressError::Zstd(error))));
+                        }
+                        false
+                    },
+                    Err(frame) => {
+                        *trailers = frame.into_trailers().ok();
+                        true
+                    },
+                },
+                task::Poll::Ready(Some(Err(error))) => return task::Poll::Ready(Some(Err(CompressError::Inner(error)))),
+                task::Poll::Ready(None) => true,
+                task::Poll::Pending => return task::Poll::Pending,
+            };
+
+            let output = match is_end {
+                true => match encoder.take() {
+                    Some(encoder) => match encoder.finish() {
+                        Ok(output) => output,
+                        Err(error) => return task::Poll::Ready(Some(Err(CompressError::Zstd(error)))),
+                    },
+                    None => unreach!(),
+                },
+                false => mem::take(writer.get_mut()),
+            };
+            if !output.is_empty() {
+                return task::Poll::Ready(Some(Ok(Frame::data(output.into()))));
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.encoder.is_none() && self.trailers.is_none()
+    }
+}
@@ -0,0 +1,262 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+use super::Collector;
+
+#[derive(Debug)]
+///`CsvCollector` error
+pub enum CsvError {
+    ///Field is not valid `UTF-8`
+    InvalidUtf8 {
+        ///Record number, starting from 1
+        record: usize,
+    },
+    ///Closing quote is followed by something other than delimiter or line end
+    InvalidQuote {
+        ///Record number, starting from 1
+        record: usize,
+    },
+    ///Body ended inside quoted field
+    UnterminatedQuote,
+    ///Record has different number of fields than the first one
+    FieldCount {
+        ///Record number, starting from 1
+        record: usize,
+        ///Expected number of fields
+        expected: usize,
+        ///Actual number of fields
+        actual: usize,
+    },
+}
+
+impl fmt::Display for CsvError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8 { record } => fmt.write_fmt(format_args!("CsvInvalidUtf8(record {})", record)),
+            Self::InvalidQuote { record } => fmt.write_fmt(format_args!("CsvInvalidQuote(record {})", record)),
+            Self::UnterminatedQuote => fmt.write_str("CsvUnterminatedQuote"),
+            Self::FieldCount { record, expected, actual } => fmt.write_fmt(format_args!("CsvFieldCount(record {} has {} fields, expected {})", record, actual, expected)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CsvError {}
+
+///Parsed CSV content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRecords {
+    headers: Option<Vec<String>>,
+    rows: Vec<Vec<String>>,
+}
+
+impl CsvRecords {
+    #[inline(always)]
+    ///Returns header row, if collector was configured to expect it
+    pub fn headers(&self) -> Option<&[String]> {
+        self.headers.as_deref()
+    }
+
+    #[inline(always)]
+    ///Returns data rows
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    #[inline(always)]
+    ///Returns header row and data rows
+    pub fn into_parts(self) -> (Option<Vec<String>>, Vec<Vec<String>>) {
+        (self.headers, self.rows)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    //Quote inside quoted field, which either closes it or escapes next quote
+    QuotedQuote,
+}
+
+///Collector, that incrementally parses CSV (RFC 4180) body into records.
+///
+///Parsing happens as frames arrive, so quoted fields may span frames and contain line breaks.
+///Both `\n` and `\r\n` line endings are accepted, and empty lines are skipped.
+///Every record must have the same number of fields as the first one.
+pub struct CsvCollector {
+    delimiter: u8,
+    has_headers: bool,
+    state: State,
+    field: Vec<u8>,
+    row: Vec<String>,
+    headers: Option<Vec<String>>,
+    rows: Vec<Vec<String>>,
+    fields_num: Option<usize>,
+    record: usize,
+    len: usize,
+}
+
+impl CsvCollector {
+    #[inline(always)]
+    ///Creates new instance, with comma delimiter and no header row
+    pub const fn new() -> Self {
+        Self {
+            delimiter: b',',
+            has_headers: false,
+            state: State::FieldStart,
+            field: Vec::new(),
+            row: Vec::new(),
+            headers: None,
+            rows: Vec::new(),
+            fields_num: None,
+            record: 1,
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    ///Sets field delimiter
+    pub const fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    #[inline(always)]
+    ///Treats first record as header row, returned separately from data rows
+    pub const fn with_headers(mut self) -> Self {
+        self.has_headers = true;
+        self
+    }
+
+    fn end_field(&mut self) -> Result<(), CsvError> {
+        let field = mem::take(&mut self.field);
+        match String::from_utf8(field) {
+            Ok(field) => {
+                self.row.push(field);
+                self.state = State::FieldStart;
+                Ok(())
+            },
+            Err(_) => Err(CsvError::InvalidUtf8 {
+                record: self.record,
+            }),
+        }
+    }
+
+    fn end_row(&mut self) -> Result<(), CsvError> {
+        if self.state == State::FieldStart && self.row.is_empty() {
+            //Empty line
+            return Ok(());
+        }
+        self.end_field()?;
+
+        let row = mem::take(&mut self.row);
+        match self.fields_num {
+            Some(expected) if expected != row.len() => return Err(CsvError::FieldCount {
+                record: self.record,
+                expected,
+                actual: row.len(),
+            }),
+            Some(_) => (),
+            None => self.fields_num = Some(row.len()),
+        }
+
+        match self.has_headers && self.headers.is_none() {
+            true => self.headers = Some(row),
+            false => self.rows.push(row),
+        }
+        self.record += 1;
+        Ok(())
+    }
+
+    fn parse(&mut self, data: &[u8]) -> Result<(), CsvError> {
+        for byte in data.iter().copied() {
+            match self.state {
+                State::FieldStart => match byte {
+                    b'"' => self.state = State::Quoted,
+                    b'\r' | b'\n' => self.end_row()?,
+                    byte if byte == self.delimiter => self.end_field()?,
+                    byte => {
+                        self.field.push(byte);
+                        self.state = State::Unquoted;
+                    },
+                },
+                State::Unquoted => match byte {
+                    b'\r' | b'\n' => self.end_row()?,
+                    byte if byte == self.delimiter => self.end_field()?,
+                    byte => self.field.push(byte),
+                },
+                State::Quoted => match byte {
+                    b'"' => self.state = State::QuotedQuote,
+                    byte => self.field.push(byte),
+                },
+                State::QuotedQuote => match byte {
+                    b'"' => {
+                        self.field.push(b'"');
+                        self.state = State::Quoted;
+                    },
+                    b'\r' | b'\n' => self.end_row()?,
+                    byte if byte == self.delimiter => self.end_field()?,
+                    _ => return Err(CsvError::InvalidQuote {
+                        record: self.record,
+                    }),
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for CsvCollector {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for CsvCollector {
+    type Output = CsvRecords;
+    type Error = CsvError;
+
+    #[inline]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.len += data.len();
+        self.parse(&data).err()
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        let result = match self.state {
+            State::Quoted => Err(CsvError::UnterminatedQuote),
+            _ => self.end_row(),
+        };
+
+        //Next body starts from scratch, expecting header row again
+        self.state = State::FieldStart;
+        self.field.clear();
+        self.row.clear();
+        self.fields_num = None;
+        self.record = 1;
+        self.len = 0;
+        let headers = self.headers.take();
+        let rows = mem::take(&mut self.rows);
+
+        result.map(|_| CsvRecords {
+            headers,
+            rows,
+        })
+    }
+}
@@ -0,0 +1,61 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::mem;
+
+use super::Collector;
+
+///Collector, that buffers body and decodes it into typed value using `decoder` on `consume`.
+///
+///Allows to plug any deserializer operating on slice, e.g. `rmp_serde::from_slice` for MessagePack:
+///
+///```rust,ignore
+///let collector = DecodeCollector::new(rmp_serde::from_slice::<Payload>);
+///```
+///
+///For JSON use `JsonCollector`, available with `json` feature.
+pub struct DecodeCollector<F> {
+    decoder: F,
+    buffer: Vec<u8>,
+}
+
+impl<F> DecodeCollector<F> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(decoder: F) -> Self {
+        Self {
+            decoder,
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl<O: Unpin, E, F: FnMut(&[u8]) -> Result<O, E> + Unpin> Collector for DecodeCollector<F> {
+    type Output = O;
+    type Error = E;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.buffer.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        (self.decoder)(&mem::take(&mut self.buffer))
+    }
+}
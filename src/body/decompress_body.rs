@@ -0,0 +1,257 @@
+extern crate alloc;
+
+#[cfg(feature = "compress")]
+use alloc::vec::Vec;
+use core::pin::Pin;
+use core::{fmt, mem, task};
+
+use super::{HttpBody, Frame, SizeHint, DecompressCollector, DecompressError};
+
+#[derive(Debug)]
+///`DecompressBody` error
+pub enum DecompressBodyError<E> {
+    ///Underlying body error
+    Inner(E),
+    ///Decompression failed
+    Decompress(DecompressError),
+}
+
+impl<E: fmt::Display> fmt::Display for DecompressBodyError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inner(error) => fmt::Display::fmt(error, fmt),
+            Self::Decompress(error) => fmt::Display::fmt(error, fmt),
+        }
+    }
+}
+
+impl<E: fmt::Display + fmt::Debug> std::error::Error for DecompressBodyError<E> {}
+
+enum DecompressState {
+    Uninit(bytes::BytesMut),
+    Plain,
+    #[cfg(feature = "compress")]
+    Zstd(zstd::stream::write::Decoder<'static, Vec<u8>>),
+    #[cfg(feature = "gzip")]
+    Inflate(super::inflate::InflateDecoder),
+    #[cfg(feature = "lz4")]
+    Lz4(super::lz4::Lz4Decoder),
+    Finished,
+}
+
+impl DecompressState {
+    //`limit` is total output limit, as detection happens before any output
+    #[cfg_attr(not(any(feature = "gzip", feature = "lz4")), allow(unused_variables))]
+    fn detect(buffer: &[u8], limit: usize) -> Result<Self, DecompressError> {
+        #[cfg(feature = "gzip")]
+        if buffer.starts_with(&DecompressCollector::GZIP_HEADER) {
+            let mut decoder = super::inflate::InflateDecoder::gzip();
+            decoder.set_limit(limit);
+            return Ok(Self::Inflate(decoder));
+        }
+        #[cfg(feature = "lz4")]
+        if buffer.starts_with(&DecompressCollector::LZ4_HEADER) {
+            let mut decoder = super::lz4::Lz4Decoder::new();
+            decoder.set_limit(limit);
+            return Ok(Self::Lz4(decoder));
+        }
+
+        #[cfg(feature = "compress")]
+        if buffer.starts_with(&DecompressCollector::ZSTD_HEADER) {
+            return DecompressCollector::new_zstd(None).map(Self::Zstd);
+        }
+
+        Ok(Self::Plain)
+    }
+
+    //Returns decompressed data, which may be empty, failing if it exceeds `remaining` bytes
+    fn write(&mut self, data: bytes::Bytes, remaining: usize) -> Result<bytes::Bytes, DecompressError> {
+        match self {
+            Self::Uninit(buffer) => {
+                buffer.extend_from_slice(&data);
+                if buffer.len() < DecompressCollector::MAGIC_LEN {
+                    return Ok(bytes::Bytes::new());
+                }
+                let buffer = mem::take(buffer).freeze();
+                *self = Self::detect(&buffer, remaining)?;
+                self.write(buffer, remaining)
+            },
+            Self::Plain => match data.len() > remaining {
+                true => Err(DecompressError::Overflow),
+                false => Ok(data),
+            },
+            #[cfg(feature = "compress")]
+            Self::Zstd(decoder) => match DecompressCollector::write_zstd(decoder, &data, remaining) {
+                Some(error) => Err(error),
+                None => Ok(mem::take(decoder.get_mut()).into()),
+            },
+            #[cfg(feature = "gzip")]
+            Self::Inflate(decoder) => match decoder.write_all(&data) {
+                Ok(()) => Ok(decoder.take_output().into()),
+                Err(error) => Err(error),
+            },
+            #[cfg(feature = "lz4")]
+            Self::Lz4(decoder) => match decoder.write_all(&data) {
+                Ok(()) => Ok(decoder.take_output().into()),
+                Err(error) => Err(error),
+            },
+            Self::Finished => Ok(bytes::Bytes::new()),
+        }
+    }
+
+    //Returns remaining decompressed data, which may be empty, failing if it exceeds `remaining` bytes
+    fn end(self, remaining: usize) -> Result<bytes::Bytes, DecompressError> {
+        match self {
+            //Body is too short to be compressed
+            Self::Uninit(buffer) if buffer.len() > remaining => Err(DecompressError::Overflow),
+            Self::Uninit(buffer) => Ok(buffer.freeze()),
+            Self::Plain | Self::Finished => Ok(bytes::Bytes::new()),
+            #[cfg(feature = "compress")]
+            Self::Zstd(mut decoder) => match std::io::Write::flush(&mut decoder) {
+                Ok(()) if decoder.get_ref().len() > remaining => Err(DecompressError::Overflow),
+                Ok(()) => Ok(mem::take(decoder.get_mut()).into()),
+                Err(error) => Err(DecompressError::Zstd(error)),
+            },
+            #[cfg(feature = "gzip")]
+            Self::Inflate(mut decoder) => match decoder.end() {
+                Ok(()) => Ok(decoder.take_output().into()),
+                Err(error) => Err(error),
+            },
+            #[cfg(feature = "lz4")]
+            Self::Lz4(mut decoder) => match decoder.end() {
+                Ok(()) => Ok(decoder.take_output().into()),
+                Err(error) => Err(error),
+            },
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that decompresses underlying body as it arrives, emitting decompressed data frames.
+    ///
+    ///Algorithm is detected from leading bytes, same as `DecompressCollector`, and content without known magic number is passed through as it is.
+    ///Unlike `DecompressCollector`, decompressed content is not accumulated, but emitted as soon as it is decoded.
+    ///
+    ///Decoders still keep their back-reference window (`32KiB` for `gzip`, `64KiB` for `lz4`, up to `128MiB` for `zstd`),
+    ///while `lz4` additionally buffers compressed input of incomplete block.
+    ///Use `with_limit` to bound decompressed size.
+    ///
+    ///Trailers are emitted after all decompressed data. Once error is returned, body ends.
+    #[project = DecompressBodyProj]
+    pub struct DecompressBody<T> {
+        #[pin]
+        body: T,
+        decoding: Decoding,
+    }
+}
+
+//Part of `DecompressBody`, that is not pinned
+struct Decoding {
+    state: DecompressState,
+    trailers: Option<http::HeaderMap>,
+    is_finished: bool,
+    //Total output emitted so far and its limit
+    emitted: usize,
+    limit: usize,
+}
+
+impl Decoding {
+    #[inline(always)]
+    fn remaining(&self) -> usize {
+        self.limit.saturating_sub(self.emitted)
+    }
+
+    #[inline(always)]
+    fn emit<E>(&mut self, data: bytes::Bytes) -> task::Poll<Option<Result<Frame<bytes::Bytes>, DecompressBodyError<E>>>> {
+        self.emitted = self.emitted.saturating_add(data.len());
+        task::Poll::Ready(Some(Ok(Frame::data(data))))
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn fail<E>(&mut self, error: DecompressBodyError<E>) -> task::Poll<Option<Result<Frame<bytes::Bytes>, DecompressBodyError<E>>>> {
+        self.state = DecompressState::Finished;
+        self.trailers = None;
+        self.is_finished = true;
+        task::Poll::Ready(Some(Err(error)))
+    }
+}
+
+impl<T> DecompressBody<T> {
+    #[inline(always)]
+    ///Creates new instance
+    pub fn new(body: T) -> Self {
+        Self {
+            body,
+            decoding: Decoding {
+                state: DecompressState::Uninit(bytes::BytesMut::new()),
+                trailers: None,
+                is_finished: false,
+                emitted: 0,
+                limit: usize::MAX,
+            },
+        }
+    }
+
+    #[inline(always)]
+    ///Sets limit of decompressed content, exceeding which fails body with `DecompressError::Overflow`.
+    ///
+    ///`gzip` and `lz4` decoders check limit while decoding, hence never produce more than `max_out` bytes,
+    ///while `zstd` checks it after every write into decoder, which produces at most single block.
+    pub fn with_limit(mut self, max_out: usize) -> Self {
+        self.decoding.limit = max_out;
+        self
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>> HttpBody for DecompressBody<T> {
+    type Data = bytes::Bytes;
+    type Error = DecompressBodyError<T::Error>;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let DecompressBodyProj { mut body, decoding } = self.project();
+
+        loop {
+            if decoding.is_finished {
+                match mem::replace(&mut decoding.state, DecompressState::Finished) {
+                    DecompressState::Finished => break task::Poll::Ready(decoding.trailers.take().map(|trailers| Ok(Frame::trailers(trailers)))),
+                    state => match state.end(decoding.remaining()) {
+                        Ok(data) if data.is_empty() => continue,
+                        Ok(data) => break decoding.emit(data),
+                        Err(error) => break decoding.fail(DecompressBodyError::Decompress(error)),
+                    },
+                }
+            }
+
+            match HttpBody::poll_frame(body.as_mut(), cx) {
+                task::Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                    Ok(data) => match decoding.state.write(data, decoding.remaining()) {
+                        Ok(data) if data.is_empty() => continue,
+                        Ok(data) => break decoding.emit(data),
+                        Err(error) => break decoding.fail(DecompressBodyError::Decompress(error)),
+                    },
+                    Err(frame) => if let Ok(trailers) = frame.into_trailers() {
+                        match decoding.trailers.as_mut() {
+                            Some(existing) => existing.extend(trailers),
+                            None => decoding.trailers = Some(trailers),
+                        }
+                    },
+                },
+                task::Poll::Ready(Some(Err(error))) => break decoding.fail(DecompressBodyError::Inner(error)),
+                task::Poll::Ready(None) => decoding.is_finished = true,
+                task::Poll::Pending => break task::Poll::Pending,
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.decoding.is_finished && self.decoding.trailers.is_none() && matches!(self.decoding.state, DecompressState::Finished)
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::new()
+    }
+}
@@ -0,0 +1,73 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use super::{Collector, DecompressCollector, DecompressError};
+
+///`DecompressCollector` wrapper, that reports progress after every append and allows to abort decompression.
+///
+///`callback` is invoked with total number of received (compressed) bytes and total number of produced (decompressed) bytes.
+///Number of produced bytes is the same as `DecompressCollector::len`, hence it may lag behind, as decoder buffers its output.
+///After that `abort` flag is checked, and if set, collection fails with `DecompressError::Cancelled`.
+///
+///Flag can be set from anywhere, including `callback` itself, which allows to stop decompression bombs by observed expansion ratio.
+pub struct DecompressProgressCollector<F> {
+    inner: DecompressCollector,
+    input: usize,
+    abort: Arc<AtomicBool>,
+    callback: F,
+}
+
+impl<F: FnMut(usize, usize)> DecompressProgressCollector<F> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(inner: DecompressCollector, abort: Arc<AtomicBool>, callback: F) -> Self {
+        Self {
+            inner,
+            input: 0,
+            abort,
+            callback,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns total number of received bytes
+    pub const fn input_len(&self) -> usize {
+        self.input
+    }
+}
+
+impl<F: FnMut(usize, usize) + Unpin> Collector for DecompressProgressCollector<F> {
+    type Output = Vec<u8>;
+    type Error = DecompressError;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.input = self.input.saturating_add(data.len());
+        if let Some(error) = self.inner.append(data) {
+            return Some(error);
+        }
+
+        (self.callback)(self.input, self.inner.len());
+        match self.abort.load(Ordering::Acquire) {
+            true => Some(DecompressError::Cancelled),
+            false => None,
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, headers: http::HeaderMap) {
+        self.inner.on_trailers(headers)
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        self.inner.consume()
+    }
+}
@@ -0,0 +1,149 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+use super::Collector;
+
+///AEAD cipher interface, used by `DecryptCollector`.
+///
+///Intended to be implemented as thin adapter over existing implementation (e.g. `aes-gcm` or `chacha20poly1305`)
+pub trait Aead {
+    ///Nonce size in bytes.
+    const NONCE_LEN: usize;
+    ///Authentication tag size in bytes.
+    const TAG_LEN: usize;
+    ///Decryption error
+    type Error;
+
+    ///Decrypts `buffer`, consisting of ciphertext followed by authentication tag.
+    ///
+    ///On success `buffer` must contain only plaintext.
+    ///Implementation must verify authentication tag before exposing any plaintext.
+    fn decrypt_in_place(&self, nonce: &[u8], aad: &[u8], buffer: &mut Vec<u8>) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug)]
+///`DecryptCollector` error
+pub enum DecryptError<E> {
+    ///Body is too short to contain nonce and authentication tag.
+    TooShort,
+    ///Cipher failed to decrypt body, most likely due to authentication tag mismatch.
+    Cipher(E),
+}
+
+impl<E: fmt::Display> fmt::Display for DecryptError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TooShort => fmt.write_str("CiphertextTooShort"),
+            Self::Cipher(error) => fmt::Display::fmt(error, fmt),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display + fmt::Debug> std::error::Error for DecryptError<E> {}
+
+enum Nonce {
+    Explicit(Vec<u8>),
+    Prefixed,
+}
+
+///Collector, that buffers ciphertext and decrypts it using AEAD cipher on `consume`.
+///
+///Body is expected to be ciphertext followed by authentication tag, optionally prefixed by nonce (see `prefixed`).
+///Plaintext is only returned after successful tag verification.
+///
+///Note that `Collect` limit applies to ciphertext.
+pub struct DecryptCollector<A> {
+    cipher: A,
+    nonce: Nonce,
+    aad: Vec<u8>,
+    buffer: Vec<u8>,
+}
+
+impl<A: Aead> DecryptCollector<A> {
+    #[inline]
+    ///Creates new instance with explicit `nonce`.
+    ///
+    ///Never re-use the same nonce with the same key on sender's side.
+    ///
+    ///Panics if `nonce` length is not `A::NONCE_LEN`
+    pub fn new(cipher: A, nonce: &[u8]) -> Self {
+        assert_eq!(nonce.len(), A::NONCE_LEN, "Invalid nonce length");
+        Self {
+            cipher,
+            nonce: Nonce::Explicit(nonce.into()),
+            aad: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    ///Creates new instance, that reads nonce from first `A::NONCE_LEN` bytes of body.
+    pub const fn prefixed(cipher: A) -> Self {
+        Self {
+            cipher,
+            nonce: Nonce::Prefixed,
+            aad: Vec::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    ///Sets additional authenticated data, that must match sender's.
+    pub fn with_aad(mut self, aad: &[u8]) -> Self {
+        self.aad = aad.into();
+        self
+    }
+}
+
+impl<A: Aead + Unpin> Collector for DecryptCollector<A> {
+    type Output = Vec<u8>;
+    type Error = DecryptError<A::Error>;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.buffer.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        let mut buffer = mem::take(&mut self.buffer);
+        let result = match &self.nonce {
+            Nonce::Explicit(nonce) => match buffer.len() < A::TAG_LEN {
+                true => return Err(DecryptError::TooShort),
+                false => self.cipher.decrypt_in_place(nonce, &self.aad, &mut buffer),
+            },
+            Nonce::Prefixed => match buffer.len() < A::NONCE_LEN + A::TAG_LEN {
+                true => return Err(DecryptError::TooShort),
+                false => {
+                    let mut ciphertext = buffer.split_off(A::NONCE_LEN);
+                    let result = self.cipher.decrypt_in_place(&buffer, &self.aad, &mut ciphertext);
+                    buffer = ciphertext;
+                    result
+                }
+            },
+        };
+
+        match result {
+            Ok(()) => Ok(buffer),
+            Err(error) => Err(DecryptError::Cipher(error)),
+        }
+    }
+}
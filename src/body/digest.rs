@@ -0,0 +1,57 @@
+use core::mem;
+
+use super::Collector;
+use crate::hash::Digest;
+
+///Collector, that computes digest `D` of body, discarding content.
+///
+///Its `len` reports number of bytes hashed, so `Collect` limit is still applied.
+pub struct HashCollector<D> {
+    hasher: D,
+    len: usize,
+}
+
+impl<D: Digest> HashCollector<D> {
+    #[inline(always)]
+    ///Creates new instance
+    pub fn new() -> Self {
+        Self {
+            hasher: D::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<D: Digest> Default for HashCollector<D> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D: Digest + Unpin> Collector for HashCollector<D> where digest::Output<D>: Unpin {
+    type Output = digest::Output<D>;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        Digest::update(&mut self.hasher, &data);
+        self.len = self.len.saturating_add(data.len());
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        self.len = 0;
+        Ok(mem::replace(&mut self.hasher, D::new()).finalize())
+    }
+}
@@ -0,0 +1,117 @@
+extern crate alloc;
+
+use alloc::string::String;
+use core::{fmt, mem};
+
+use super::Collector;
+use crate::hash::{Digest, Sha256};
+
+#[derive(Debug)]
+///`DigestVerifyCollector` error
+pub enum DigestError<E> {
+    ///Underlying collector error
+    Inner(E),
+    ///Trailers contain no `SHA-256` digest
+    Missing,
+    ///Digest of body does not match trailer
+    Mismatch,
+}
+
+impl<E: fmt::Display> fmt::Display for DigestError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inner(error) => fmt::Display::fmt(error, fmt),
+            Self::Missing => fmt.write_str("DigestMissing"),
+            Self::Mismatch => fmt.write_str("DigestMismatch"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display + fmt::Debug> std::error::Error for DigestError<E> {}
+
+///Collector wrapper, that verifies body against `SHA-256` digest sent in trailers.
+///
+///Following trailers are recognized, with `Content-Digest` being preferred:
+///- `Content-Digest: sha-256=:<base64>:` (RFC 9530)
+///- `Digest: sha-256=<base64>` (RFC 3230)
+///
+///Verification happens on `consume`, and fails if no digest is received.
+pub struct DigestVerifyCollector<C> {
+    inner: C,
+    hasher: Sha256,
+    expected: Option<String>,
+}
+
+impl<C> DigestVerifyCollector<C> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(inner: C) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            expected: None,
+        }
+    }
+
+    fn find_sha256(value: &http::HeaderValue) -> Option<&str> {
+        let value = value.to_str().ok()?;
+        for digest in value.split(',') {
+            if let Some((algorithm, digest)) = digest.split_once('=') {
+                if algorithm.trim().eq_ignore_ascii_case("sha-256") {
+                    return Some(digest.trim().trim_matches(':').trim_end_matches('='));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<C: Collector> Collector for DigestVerifyCollector<C> {
+    type Output = C::Output;
+    type Error = DigestError<C::Error>;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.hasher.update(&data);
+        self.inner.append(data).map(DigestError::Inner)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    fn on_trailers(&mut self, headers: http::HeaderMap) {
+        let content_digest = headers.get_all("content-digest").iter().find_map(Self::find_sha256);
+        let digest = || headers.get_all("digest").iter().find_map(Self::find_sha256);
+        if let Some(expected) = content_digest.or_else(digest) {
+            self.expected = Some(expected.into());
+        }
+
+        self.inner.on_trailers(headers)
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        //State is reset regardless of verification result, so that next body is verified on its own
+        let digest = mem::take(&mut self.hasher).finalize();
+        let output = self.inner.consume().map_err(DigestError::Inner);
+        let expected = match self.expected.take() {
+            Some(expected) => expected,
+            None => return Err(DigestError::Missing),
+        };
+
+        let mut actual = String::with_capacity(44);
+        crate::hash::base64_encode(&digest, &mut actual);
+        match actual.trim_end_matches('=') == expected {
+            true => output,
+            false => Err(DigestError::Mismatch),
+        }
+    }
+}
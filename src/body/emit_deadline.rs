@@ -0,0 +1,109 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use core::future::Future;
+use core::pin::Pin;
+use core::time::Duration;
+use core::{fmt, task};
+
+use super::{HttpBody, Frame, SizeHint};
+
+#[derive(Debug)]
+///`EmitDeadlineBody` error
+pub enum EmitDeadlineError<E> {
+    ///Underlying body error
+    Inner(E),
+    ///Body is not finished within deadline
+    DeadlineExceeded,
+}
+
+impl<E: fmt::Display> fmt::Display for EmitDeadlineError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inner(error) => fmt::Display::fmt(error, fmt),
+            Self::DeadlineExceeded => fmt.write_str("DeadlineExceeded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display + fmt::Debug> std::error::Error for EmitDeadlineError<E> {}
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that requires underlying body to be emitted completely within `timeout`.
+    ///
+    ///Timer starts on the first poll and is checked before polling underlying body, so that even body, that is always ready, is cut off.
+    ///Once deadline passes, `EmitDeadlineError::DeadlineExceeded` is returned and body ends.
+    ///
+    ///Timer is created using `F`, hence it is not tied to any particular runtime (e.g. `tokio::time::sleep` can be used).
+    #[project = EmitDeadlineBodyProj]
+    pub struct EmitDeadlineBody<T, F, S> {
+        #[pin]
+        body: T,
+        timeout: Duration,
+        new_sleep: F,
+        sleep: Option<Pin<Box<S>>>,
+        is_finished: bool,
+    }
+}
+
+impl<T, F: FnMut(Duration) -> S, S: Future<Output = ()>> EmitDeadlineBody<T, F, S> {
+    #[inline(always)]
+    ///Creates new instance, requiring `body` to finish within `timeout`
+    pub const fn new(body: T, timeout: Duration, new_sleep: F) -> Self {
+        Self {
+            body,
+            timeout,
+            new_sleep,
+            sleep: None,
+            is_finished: false,
+        }
+    }
+}
+
+impl<T: HttpBody, F: FnMut(Duration) -> S, S: Future<Output = ()>> HttpBody for EmitDeadlineBody<T, F, S> {
+    type Data = T::Data;
+    type Error = EmitDeadlineError<T::Error>;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let EmitDeadlineBodyProj { body, timeout, new_sleep, sleep, is_finished } = self.project();
+        if *is_finished {
+            return task::Poll::Ready(None);
+        }
+
+        let timer = match sleep.as_mut() {
+            Some(timer) => timer,
+            None => sleep.insert(Box::pin((*new_sleep)(*timeout))),
+        };
+        if Future::poll(timer.as_mut(), cx).is_ready() {
+            *is_finished = true;
+            *sleep = None;
+            return task::Poll::Ready(Some(Err(EmitDeadlineError::DeadlineExceeded)));
+        }
+
+        match HttpBody::poll_frame(body, cx) {
+            task::Poll::Ready(Some(Ok(frame))) => task::Poll::Ready(Some(Ok(frame))),
+            task::Poll::Ready(Some(Err(error))) => task::Poll::Ready(Some(Err(EmitDeadlineError::Inner(error)))),
+            task::Poll::Ready(None) => {
+                *is_finished = true;
+                *sleep = None;
+                task::Poll::Ready(None)
+            },
+            task::Poll::Pending => task::Poll::Pending,
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.is_finished || self.body.is_end_stream()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        match self.is_finished {
+            true => SizeHint::with_exact(0),
+            false => self.body.size_hint(),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+use super::Collector;
+
+#[derive(Debug)]
+///`FormCollector` error
+pub enum FormError {
+    ///Decoded key or value is not valid `UTF-8`
+    InvalidUtf8,
+}
+
+impl fmt::Display for FormError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8 => fmt.write_str("FormInvalidUtf8"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FormError {}
+
+#[inline(always)]
+const fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+///Percent-decodes `data`, optionally treating `+` as space.
+///
+///Malformed escapes are kept as they are.
+pub(crate) fn percent_decode(data: &[u8], is_plus_space: bool) -> Vec<u8> {
+    let mut result = Vec::with_capacity(data.len());
+    let mut idx = 0;
+    while idx < data.len() {
+        match data[idx] {
+            b'%' => match (data.get(idx + 1).copied().and_then(hex_value), data.get(idx + 2).copied().and_then(hex_value)) {
+                (Some(high), Some(low)) => {
+                    result.push((high << 4) | low);
+                    idx += 3;
+                    continue;
+                },
+                _ => result.push(b'%'),
+            },
+            b'+' if is_plus_space => result.push(b' '),
+            byte => result.push(byte),
+        }
+        idx += 1;
+    }
+    result
+}
+
+///Parses `application/x-www-form-urlencoded` data into list of key-value pairs, preserving order.
+///
+///Empty pairs are skipped, and pair without `=` is treated as key with empty value.
+pub fn parse_form(data: &[u8]) -> Result<Vec<(String, String)>, FormError> {
+    let decode = |data: &[u8]| String::from_utf8(percent_decode(data, true)).map_err(|_| FormError::InvalidUtf8);
+
+    let mut result = Vec::new();
+    for pair in data.split(|byte| *byte == b'&') {
+        if pair.is_empty() {
+            continue;
+        }
+
+        let (key, value) = match pair.iter().position(|byte| *byte == b'=') {
+            Some(idx) => (&pair[..idx], &pair[idx + 1..]),
+            None => (pair, &[][..]),
+        };
+        result.push((decode(key)?, decode(value)?));
+    }
+
+    Ok(result)
+}
+
+///Collector, that parses `application/x-www-form-urlencoded` body into list of key-value pairs.
+///
+///See `parse_form` for details.
+pub struct FormCollector {
+    buffer: Vec<u8>,
+}
+
+impl FormCollector {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+        }
+    }
+}
+
+impl Default for FormCollector {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for FormCollector {
+    type Output = Vec<(String, String)>;
+    type Error = FormError;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.buffer.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        parse_form(&mem::take(&mut self.buffer))
+    }
+}
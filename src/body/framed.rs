@@ -0,0 +1,128 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+use bytes::Buf;
+
+use super::Collector;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Length prefix format of `FramedCollector` messages
+pub enum LengthPrefix {
+    ///Little-endian `u16`
+    U16Le,
+    ///Big-endian `u16`
+    U16Be,
+    ///Little-endian `u32`
+    U32Le,
+    ///Big-endian `u32`
+    U32Be,
+}
+
+impl LengthPrefix {
+    #[inline(always)]
+    ///Returns number of bytes taken by prefix
+    pub const fn width(self) -> usize {
+        match self {
+            Self::U16Le | Self::U16Be => 2,
+            Self::U32Le | Self::U32Be => 4,
+        }
+    }
+
+    #[inline(always)]
+    fn read(self, bytes: &[u8]) -> usize {
+        match self {
+            Self::U16Le => u16::from_le_bytes([bytes[0], bytes[1]]) as usize,
+            Self::U16Be => u16::from_be_bytes([bytes[0], bytes[1]]) as usize,
+            Self::U32Le => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+            Self::U32Be => u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+///`FramedCollector` error
+pub enum FramedError {
+    ///Body ended in the middle of message
+    Incomplete {
+        ///Number of trailing bytes, that do not form complete message
+        remaining: usize,
+    },
+}
+
+impl fmt::Display for FramedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Incomplete { remaining } => fmt.write_fmt(format_args!("Incomplete({} trailing bytes)", remaining)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FramedError {}
+
+///Collector of length prefixed messages `[len][payload][len][payload]...`
+///
+///Prefixes and payloads may span across frames of body.
+pub struct FramedCollector {
+    prefix: LengthPrefix,
+    buffer: bytes::BytesMut,
+    messages: Vec<bytes::Bytes>,
+    len: usize,
+}
+
+impl FramedCollector {
+    #[inline(always)]
+    ///Creates new instance, with specified `prefix` format
+    pub fn new(prefix: LengthPrefix) -> Self {
+        Self {
+            prefix,
+            buffer: bytes::BytesMut::new(),
+            messages: Vec::new(),
+            len: 0,
+        }
+    }
+}
+
+impl Collector for FramedCollector {
+    type Output = Vec<bytes::Bytes>;
+    type Error = FramedError;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.len = self.len.saturating_add(data.len());
+        self.buffer.extend_from_slice(&data);
+
+        let width = self.prefix.width();
+        while self.buffer.len() >= width {
+            let size = self.prefix.read(&self.buffer);
+            if self.buffer.len() - width < size {
+                break;
+            }
+
+            self.buffer.advance(width);
+            self.messages.push(self.buffer.split_to(size).freeze());
+        }
+
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        self.len = 0;
+        match self.buffer.len() {
+            0 => Ok(mem::take(&mut self.messages)),
+            remaining => Err(FramedError::Incomplete {
+                remaining,
+            }),
+        }
+    }
+}
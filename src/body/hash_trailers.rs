@@ -0,0 +1,105 @@
+extern crate alloc;
+
+use alloc::string::String;
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+use crate::hash::{Digest, Sha256};
+
+///Name of trailer, containing hex encoded `SHA-256` digest of body
+pub const CHECKSUM_SHA256: http::HeaderName = http::HeaderName::from_static("x-checksum-sha256");
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that passes through data while hashing it, and emits trailers with `X-Checksum-SHA256` once body ends.
+    ///
+    ///If underlying body emits own trailers, checksum is added to them.
+    ///
+    ///Size hint reports only lower bound until trailers are emitted, as exact size makes `hyper` send `Content-Length` and drop trailers over `HTTP/1.1`.
+    #[project = HashTrailersBodyProj]
+    pub struct HashTrailersBody<T> {
+        #[pin]
+        body: T,
+        hasher: Option<Sha256>,
+    }
+}
+
+impl<T> HashTrailersBody<T> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(body: T) -> Self {
+        Self {
+            body,
+            hasher: Some(Sha256::new()),
+        }
+    }
+
+    fn checksum(hasher: Sha256) -> http::HeaderValue {
+        let mut checksum = String::with_capacity(64);
+        crate::hash::hex_encode(&hasher.finalize(), &mut checksum);
+        match http::HeaderValue::from_str(&checksum) {
+            Ok(checksum) => checksum,
+            Err(_) => unreach!(),
+        }
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>> HttpBody for HashTrailersBody<T> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let HashTrailersBodyProj { body, hasher } = self.project();
+
+        let digest = match hasher.as_mut() {
+            Some(digest) => digest,
+            None => return task::Poll::Ready(None),
+        };
+
+        match HttpBody::poll_frame(body, cx) {
+            task::Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                Ok(data) => {
+                    digest.update(&data);
+                    task::Poll::Ready(Some(Ok(Frame::data(data))))
+                },
+                Err(frame) => match frame.into_trailers() {
+                    Ok(mut trailers) => match hasher.take() {
+                        Some(hasher) => {
+                            trailers.insert(CHECKSUM_SHA256, Self::checksum(hasher));
+                            task::Poll::Ready(Some(Ok(Frame::trailers(trailers))))
+                        },
+                        None => unreach!(),
+                    },
+                    Err(_) => unreach!(),
+                }
+            },
+            task::Poll::Ready(Some(Err(error))) => task::Poll::Ready(Some(Err(error))),
+            task::Poll::Ready(None) => match hasher.take() {
+                Some(hasher) => {
+                    let mut trailers = http::HeaderMap::with_capacity(1);
+                    trailers.insert(CHECKSUM_SHA256, Self::checksum(hasher));
+                    task::Poll::Ready(Some(Ok(Frame::trailers(trailers))))
+                },
+                None => unreach!(),
+            },
+            task::Poll::Pending => task::Poll::Pending,
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.hasher.is_none()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        match self.hasher {
+            Some(_) => {
+                let mut hint = SizeHint::new();
+                hint.set_lower(self.body.size_hint().lower());
+                hint
+            },
+            None => SizeHint::with_exact(0),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+use super::Collector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///`HeaderBlockCollector` error
+pub enum HeaderBlockError {
+    ///Line is not in `Name: Value` form
+    InvalidLine {
+        ///Line number, starting from 1
+        line: usize,
+    },
+    ///Header name is not valid
+    InvalidName {
+        ///Line number, starting from 1
+        line: usize,
+    },
+    ///Header value is not valid
+    InvalidValue {
+        ///Line number, starting from 1
+        line: usize,
+    },
+    ///Data follows empty line, that terminates header block
+    TrailingData {
+        ///Line number, starting from 1
+        line: usize,
+    },
+}
+
+impl fmt::Display for HeaderBlockError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLine { line } => fmt.write_fmt(format_args!("InvalidLine({})", line)),
+            Self::InvalidName { line } => fmt.write_fmt(format_args!("InvalidHeaderName(line {})", line)),
+            Self::InvalidValue { line } => fmt.write_fmt(format_args!("InvalidHeaderValue(line {})", line)),
+            Self::TrailingData { line } => fmt.write_fmt(format_args!("TrailingData(line {})", line)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HeaderBlockError {}
+
+///Collector, that parses body as block of `Name: Value` lines into `http::HeaderMap`.
+///
+///Lines are terminated by `\r\n` or `\n`, and are parsed as soon as they are complete, so only incomplete line is buffered.
+///Empty line terminates block, after which only empty lines are allowed.
+///Last line is not required to be terminated.
+///
+///Obsolete line folding is not supported, hence line starting with whitespace is `HeaderBlockError::InvalidLine`.
+pub struct HeaderBlockCollector {
+    headers: http::HeaderMap,
+    line: Vec<u8>,
+    line_num: usize,
+    len: usize,
+    is_terminated: bool,
+}
+
+impl HeaderBlockCollector {
+    #[inline(always)]
+    ///Creates new instance
+    pub fn new() -> Self {
+        Self {
+            headers: http::HeaderMap::new(),
+            line: Vec::new(),
+            line_num: 0,
+            len: 0,
+            is_terminated: false,
+        }
+    }
+
+    fn parse_line(&mut self) -> Result<(), HeaderBlockError> {
+        self.line_num += 1;
+        let line = self.line_num;
+        let mut data = mem::take(&mut self.line);
+        if data.last() == Some(&b'\r') {
+            data.pop();
+        }
+
+        if data.is_empty() {
+            self.is_terminated = true;
+            return Ok(());
+        } else if self.is_terminated {
+            return Err(HeaderBlockError::TrailingData { line });
+        }
+
+        let (name, value) = match data.iter().position(|byte| *byte == b':') {
+            Some(idx) => (&data[..idx], &data[idx + 1..]),
+            None => return Err(HeaderBlockError::InvalidLine { line }),
+        };
+        if matches!(name.first(), Some(b' ' | b'\t')) {
+            return Err(HeaderBlockError::InvalidLine { line });
+        }
+        let name = match http::HeaderName::from_bytes(name) {
+            Ok(name) => name,
+            Err(_) => return Err(HeaderBlockError::InvalidName { line }),
+        };
+        let value = value.trim_ascii();
+        let value = match http::HeaderValue::from_bytes(value) {
+            Ok(value) => value,
+            Err(_) => return Err(HeaderBlockError::InvalidValue { line }),
+        };
+        self.headers.append(name, value);
+
+        //Reuse allocation for the next line
+        data.clear();
+        self.line = data;
+        Ok(())
+    }
+}
+
+impl Default for HeaderBlockCollector {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for HeaderBlockCollector {
+    type Output = http::HeaderMap;
+    type Error = HeaderBlockError;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.len += data.len();
+        let mut data = &data[..];
+        while let Some(end) = data.iter().position(|byte| *byte == b'\n') {
+            self.line.extend_from_slice(&data[..end]);
+            data = &data[end + 1..];
+            if let Err(error) = self.parse_line() {
+                return Some(error);
+            }
+        }
+        self.line.extend_from_slice(data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        if !self.line.is_empty() {
+            self.parse_line()?;
+        }
+
+        self.line_num = 0;
+        self.len = 0;
+        self.is_terminated = false;
+        Ok(mem::take(&mut self.headers))
+    }
+}
@@ -0,0 +1,235 @@
+//!Streaming decoder of `DEFLATE` (RFC 1951), wrapped into either `gzip` (RFC 1952) or `zlib` (RFC 1950) format
+extern crate alloc;
+
+use alloc::vec::Vec;
+use std::io;
+
+use super::DecompressError;
+
+//Maximum size of output produced by single call into decoder
+const OUTPUT_CHUNK: usize = 32 * 1024;
+
+#[inline(always)]
+fn invalid(error: &'static str) -> DecompressError {
+    DecompressError::Gzip(io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+//Returns size of gzip member header, or `None` if it is not complete yet
+fn gzip_header(data: &[u8]) -> Result<Option<usize>, DecompressError> {
+    const FHCRC: u8 = 0x02;
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+    const FCOMMENT: u8 = 0x10;
+
+    if data.len() < 10 {
+        return Ok(None);
+    } else if data[0] != 0x1f || data[1] != 0x8b {
+        return Err(invalid("invalid gzip magic"));
+    } else if data[2] != 8 {
+        return Err(invalid("unsupported gzip compression method"));
+    }
+
+    let flags = data[3];
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let len = match data.get(pos..pos + 2) {
+            Some(len) => u16::from_le_bytes([len[0], len[1]]) as usize,
+            None => return Ok(None),
+        };
+        pos += 2 + len;
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            match data.get(pos..).and_then(|data| data.iter().position(|byte| *byte == 0)) {
+                Some(end) => pos += end + 1,
+                None => return Ok(None),
+            }
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+
+    match pos <= data.len() {
+        true => Ok(Some(pos)),
+        false => Ok(None),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Gzip,
+    Zlib,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Header,
+    Blocks,
+    Trailer,
+}
+
+///Streaming `gzip` or `zlib` decoder, backed by `flate2`.
+///
+///`gzip` may consist of multiple members, while `zlib` is single stream that cannot be followed by any data.
+///`zlib` header and `Adler-32` trailer are handled by `flate2`, while `gzip` framing is parsed here.
+///
+///Output is kept until taken via `take_output`.
+///Only `gzip` header and trailer are buffered, while compressed data is fed into decoder as it arrives.
+///
+///Total output is limited by `limit`, which is checked as data is being decoded.
+pub(crate) struct InflateDecoder {
+    format: Format,
+    decoder: flate2::Decompress,
+    //Unprocessed input
+    input: Vec<u8>,
+    output: Vec<u8>,
+    stage: Stage,
+    //Checksum and size of current `gzip` member
+    crc: flate2::Crc,
+    members: usize,
+    //Total output produced so far and its limit
+    produced: usize,
+    limit: usize,
+}
+
+impl InflateDecoder {
+    #[inline(always)]
+    pub(crate) fn gzip() -> Self {
+        Self::new(Format::Gzip)
+    }
+
+    #[inline(always)]
+    pub(crate) fn zlib() -> Self {
+        Self::new(Format::Zlib)
+    }
+
+    #[inline(always)]
+    fn new(format: Format) -> Self {
+        Self {
+            format,
+            decoder: flate2::Decompress::new(format == Format::Zlib),
+            input: Vec::new(),
+            output: Vec::new(),
+            stage: Stage::Header,
+            crc: flate2::Crc::new(),
+            members: 0,
+            produced: 0,
+            limit: usize::MAX,
+        }
+    }
+
+    #[inline(always)]
+    ///Sets limit of total output, exceeding which results in `DecompressError::Overflow`
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    #[inline(always)]
+    pub(crate) fn get_ref(&self) -> &Vec<u8> {
+        &self.output
+    }
+
+    pub(crate) fn write_all(&mut self, data: &[u8]) -> Result<(), DecompressError> {
+        self.input.extend_from_slice(data);
+        let result = self.decode();
+        self.input.drain(..result?);
+        Ok(())
+    }
+
+    #[inline(always)]
+    ///Returns output produced since last call
+    pub(crate) fn take_output(&mut self) -> Vec<u8> {
+        core::mem::take(&mut self.output)
+    }
+
+    ///Decodes remaining input, checking stream is complete
+    pub(crate) fn end(&mut self) -> Result<(), DecompressError> {
+        self.write_all(&[])?;
+        match self.stage == Stage::Header && self.input.is_empty() && self.members > 0 {
+            true => Ok(()),
+            false => Err(DecompressError::Gzip(io::Error::new(io::ErrorKind::UnexpectedEof, "stream is incomplete"))),
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> Result<Vec<u8>, DecompressError> {
+        self.end()?;
+        Ok(self.output)
+    }
+
+    //Decodes as much of input as possible, returning number of consumed bytes
+    fn decode(&mut self) -> Result<usize, DecompressError> {
+        let mut pos = 0;
+        loop {
+            let input = &self.input[pos..];
+            match self.stage {
+                Stage::Header => match self.format {
+                    _ if input.is_empty() => break Ok(pos),
+                    Format::Gzip => match gzip_header(input)? {
+                        Some(len) => {
+                            pos += len;
+                            self.decoder.reset(false);
+                            self.crc.reset();
+                            self.stage = Stage::Blocks;
+                        },
+                        None => break Ok(pos),
+                    },
+                    Format::Zlib if self.members == 0 => self.stage = Stage::Blocks,
+                    Format::Zlib => break Err(invalid("trailing data after zlib stream")),
+                },
+                Stage::Blocks => {
+                    //Allow single byte over limit to tell overflow from output of exactly limit size
+                    let remaining = self.limit.saturating_sub(self.produced);
+                    let output_len = self.output.len();
+                    self.output.resize(output_len + core::cmp::min(OUTPUT_CHUNK, remaining.saturating_add(1)), 0);
+
+                    let (total_in, total_out) = (self.decoder.total_in(), self.decoder.total_out());
+                    let status = self.decoder.decompress(input, &mut self.output[output_len..], flate2::FlushDecompress::None);
+                    let consumed = (self.decoder.total_in() - total_in) as usize;
+                    let written = (self.decoder.total_out() - total_out) as usize;
+                    self.output.truncate(output_len + written);
+                    pos += consumed;
+
+                    if written > remaining {
+                        self.output.truncate(output_len + remaining);
+                        break Err(DecompressError::Overflow);
+                    }
+                    self.produced += written;
+                    if self.format == Format::Gzip {
+                        self.crc.update(&self.output[output_len..]);
+                    }
+
+                    match status {
+                        Ok(flate2::Status::StreamEnd) => match self.format {
+                            Format::Gzip => self.stage = Stage::Trailer,
+                            Format::Zlib => {
+                                self.members += 1;
+                                self.stage = Stage::Header;
+                            },
+                        },
+                        Ok(_) if consumed == 0 && written == 0 => break Ok(pos),
+                        Ok(_) => continue,
+                        Err(error) => break Err(DecompressError::Gzip(io::Error::new(io::ErrorKind::InvalidData, error))),
+                    }
+                },
+                Stage::Trailer => {
+                    let trailer = match input.get(..8) {
+                        Some(trailer) => trailer,
+                        None => break Ok(pos),
+                    };
+                    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+                    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+                    if crc != self.crc.sum() {
+                        break Err(invalid("gzip CRC mismatch"));
+                    } else if size != self.crc.amount() {
+                        break Err(invalid("gzip size mismatch"));
+                    }
+
+                    pos += 8;
+                    self.members += 1;
+                    self.stage = Stage::Header;
+                },
+            }
+        }
+    }
+}
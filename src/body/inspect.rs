@@ -0,0 +1,54 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Collect, Collector, CollectError};
+
+///`Collect` wrapper, that invokes callback on successful output before returning it.
+///
+///Created by `Collect::inspect`
+pub struct CollectInspect<const S: usize, T, C, F> {
+    collect: Collect<S, T, C>,
+    inspect: Option<F>,
+}
+
+pin_invariants!(impl[const S: usize, T, C, F] CollectInspect<S, T, C, F> where Collect<S, T, C>: Unpin);
+
+impl<T, C, const S: usize> Collect<S, T, C> {
+    #[inline(always)]
+    ///Wraps future to call `inspect` on successful output, before returning it.
+    ///
+    ///Useful to log collected result without restructuring code.
+    pub fn inspect<F: FnOnce(&C::Output)>(self, inspect: F) -> CollectInspect<S, T, C, F> where C: Collector {
+        CollectInspect {
+            collect: self,
+            inspect: Some(inspect),
+        }
+    }
+}
+
+impl<T, C, F, const S: usize> CollectInspect<S, T, C, F> {
+    #[inline(always)]
+    ///Returns underlying future
+    pub fn into_inner(self) -> Collect<S, T, C> {
+        self.collect
+    }
+}
+
+impl<E, D: bytes::Buf, T: HttpBody<Data = D, Error = E>, C: Collector, F: FnOnce(&C::Output), const S: usize> Future for CollectInspect<S, T, C, F> {
+    type Output = Result<C::Output, CollectError<E, C::Error, C::Output>>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        project!(self => CollectInspect { #[pin] collect, inspect });
+
+        match Future::poll(collect, ctx) {
+            task::Poll::Ready(Ok(output)) => {
+                if let Some(inspect) = inspect.take() {
+                    inspect(&output);
+                }
+                task::Poll::Ready(Ok(output))
+            },
+            result => result,
+        }
+    }
+}
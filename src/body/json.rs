@@ -0,0 +1,325 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{fmt, mem, str};
+
+use super::Collector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///`JsonValidateCollector` error
+pub enum JsonError {
+    ///Unexpected byte
+    Syntax {
+        ///Offset of byte within body
+        offset: usize,
+    },
+    ///Body contains invalid `UTF-8` sequence
+    InvalidUtf8 {
+        ///Length of valid prefix of body
+        valid_up_to: usize,
+    },
+    ///Body ends before JSON value is complete
+    Incomplete,
+}
+
+impl fmt::Display for JsonError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Syntax { offset } => fmt.write_fmt(format_args!("JsonSyntax(at {})", offset)),
+            Self::InvalidUtf8 { valid_up_to } => fmt.write_fmt(format_args!("InvalidUtf8(valid up to {})", valid_up_to)),
+            Self::Incomplete => fmt.write_str("JsonIncomplete"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Number {
+    Minus,
+    Zero,
+    Int,
+    Dot,
+    Fraction,
+    Exponent,
+    ExponentSign,
+    ExponentDigits,
+}
+
+impl Number {
+    #[inline(always)]
+    const fn is_complete(self) -> bool {
+        matches!(self, Self::Zero | Self::Int | Self::Fraction | Self::ExponentDigits)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    Value,
+    ArrayFirst,
+    ObjectFirst,
+    Key,
+    Colon,
+    AfterValue,
+    String {
+        is_key: bool,
+    },
+    Escape {
+        is_key: bool,
+    },
+    Unicode {
+        is_key: bool,
+        remaining: u8,
+    },
+    Number(Number),
+    Literal(&'static [u8]),
+}
+
+const OBJECT: u8 = b'{';
+const ARRAY: u8 = b'[';
+
+///Collector, that validates body to be well-formed JSON, without building any value out of it.
+///
+///Bytes are fed through streaming state machine as they arrive, so that malformed body is rejected at the first invalid byte.
+///Only nesting of containers is tracked, using single byte per level.
+///
+///Output is raw body.
+pub struct JsonValidateCollector {
+    buffer: Vec<u8>,
+    //Length of validated UTF-8 prefix
+    valid: usize,
+    state: State,
+    nesting: Vec<u8>,
+}
+
+impl JsonValidateCollector {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            valid: 0,
+            state: State::Value,
+            nesting: Vec::new(),
+        }
+    }
+
+    #[inline(always)]
+    fn start_value(&mut self, byte: u8) -> bool {
+        self.state = match byte {
+            b'{' => {
+                self.nesting.push(OBJECT);
+                State::ObjectFirst
+            },
+            b'[' => {
+                self.nesting.push(ARRAY);
+                State::ArrayFirst
+            },
+            b'"' => State::String { is_key: false },
+            b'-' => State::Number(Number::Minus),
+            b'0' => State::Number(Number::Zero),
+            b'1'..=b'9' => State::Number(Number::Int),
+            b't' => State::Literal(b"rue"),
+            b'f' => State::Literal(b"alse"),
+            b'n' => State::Literal(b"ull"),
+            _ => return false,
+        };
+        true
+    }
+
+    #[inline(always)]
+    fn close(&mut self, kind: u8) -> bool {
+        match self.nesting.last() {
+            Some(last) if *last == kind => {
+                self.nesting.pop();
+                self.state = State::AfterValue;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    //Returns whether byte is valid
+    fn step(&mut self, byte: u8) -> bool {
+        let is_whitespace = matches!(byte, b' ' | b'\t' | b'\n' | b'\r');
+        match self.state {
+            State::Value => is_whitespace || self.start_value(byte),
+            State::ArrayFirst => is_whitespace || (byte == b']' && self.close(ARRAY)) || self.start_value(byte),
+            State::ObjectFirst => match byte {
+                b'"' => {
+                    self.state = State::String { is_key: true };
+                    true
+                },
+                b'}' => self.close(OBJECT),
+                _ => is_whitespace,
+            },
+            State::Key => match byte {
+                b'"' => {
+                    self.state = State::String { is_key: true };
+                    true
+                },
+                _ => is_whitespace,
+            },
+            State::Colon => match byte {
+                b':' => {
+                    self.state = State::Value;
+                    true
+                },
+                _ => is_whitespace,
+            },
+            State::AfterValue => match byte {
+                b',' => match self.nesting.last() {
+                    Some(&OBJECT) => {
+                        self.state = State::Key;
+                        true
+                    },
+                    Some(_) => {
+                        self.state = State::Value;
+                        true
+                    },
+                    None => false,
+                },
+                b']' => self.close(ARRAY),
+                b'}' => self.close(OBJECT),
+                _ => is_whitespace,
+            },
+            State::String { is_key } => {
+                match byte {
+                    b'"' => self.state = match is_key {
+                        true => State::Colon,
+                        false => State::AfterValue,
+                    },
+                    b'\\' => self.state = State::Escape { is_key },
+                    0..=0x1f => return false,
+                    _ => (),
+                }
+                true
+            },
+            State::Escape { is_key } => {
+                self.state = match byte {
+                    b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't' => State::String { is_key },
+                    b'u' => State::Unicode { is_key, remaining: 4 },
+                    _ => return false,
+                };
+                true
+            },
+            State::Unicode { is_key, remaining } => {
+                if !byte.is_ascii_hexdigit() {
+                    return false;
+                }
+                self.state = match remaining {
+                    1 => State::String { is_key },
+                    remaining => State::Unicode { is_key, remaining: remaining - 1 },
+                };
+                true
+            },
+            State::Number(number) => {
+                let next = match (number, byte) {
+                    (Number::Minus, b'0') => Number::Zero,
+                    (Number::Minus | Number::Int, b'0'..=b'9') => Number::Int,
+                    (Number::Zero | Number::Int, b'.') => Number::Dot,
+                    (Number::Dot | Number::Fraction, b'0'..=b'9') => Number::Fraction,
+                    (Number::Zero | Number::Int | Number::Fraction, b'e' | b'E') => Number::Exponent,
+                    (Number::Exponent, b'+' | b'-') => Number::ExponentSign,
+                    (Number::Exponent | Number::ExponentSign | Number::ExponentDigits, b'0'..=b'9') => Number::ExponentDigits,
+                    //Number is terminated by the first byte, that cannot continue it
+                    (number, byte) => {
+                        if !number.is_complete() {
+                            return false;
+                        }
+                        self.state = State::AfterValue;
+                        return self.step(byte);
+                    },
+                };
+                self.state = State::Number(next);
+                true
+            },
+            State::Literal(rest) => match rest.split_first() {
+                Some((expected, rest)) if *expected == byte => {
+                    self.state = match rest.is_empty() {
+                        true => State::AfterValue,
+                        false => State::Literal(rest),
+                    };
+                    true
+                },
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Default for JsonValidateCollector {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for JsonValidateCollector {
+    type Output = Vec<u8>;
+    type Error = JsonError;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        let offset = self.buffer.len();
+        for (idx, byte) in data.iter().enumerate() {
+            if !self.step(*byte) {
+                return Some(JsonError::Syntax {
+                    offset: offset + idx,
+                });
+            }
+        }
+
+        //Non-ASCII bytes are only accepted within strings, but still need to form valid code points
+        self.buffer.extend_from_slice(&data);
+        match str::from_utf8(&self.buffer[self.valid..]) {
+            Ok(_) => {
+                self.valid = self.buffer.len();
+                None
+            },
+            Err(error) => match error.error_len() {
+                None => {
+                    self.valid += error.valid_up_to();
+                    None
+                },
+                Some(_) => Some(JsonError::InvalidUtf8 {
+                    valid_up_to: self.valid + error.valid_up_to(),
+                }),
+            },
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        let is_complete = match self.state {
+            State::AfterValue => true,
+            State::Number(number) => number.is_complete(),
+            _ => false,
+        };
+        if !is_complete || !self.nesting.is_empty() {
+            return Err(JsonError::Incomplete);
+        } else if self.valid != self.buffer.len() {
+            return Err(JsonError::InvalidUtf8 {
+                valid_up_to: self.valid,
+            });
+        }
+
+        self.valid = 0;
+        self.state = State::Value;
+        Ok(mem::take(&mut self.buffer))
+    }
+}
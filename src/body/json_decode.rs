@@ -0,0 +1,63 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::mem;
+
+use super::Collector;
+
+///Collector, that buffers body and deserializes it into `T` from JSON on `consume`.
+///
+///Body size should be restricted by `Collect` limit, as whole body is buffered before parsing.
+pub struct JsonCollector<T> {
+    buffer: Vec<u8>,
+    _output: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonCollector<T> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            _output: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for JsonCollector<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: serde::de::DeserializeOwned + Unpin> Collector for JsonCollector<T> {
+    type Output = T;
+    type Error = serde_json::Error;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.buffer.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        serde_json::from_slice(&mem::take(&mut self.buffer))
+    }
+}
@@ -0,0 +1,97 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use core::future::Future;
+use core::pin::Pin;
+use core::task;
+use core::time::Duration;
+
+use super::{HttpBody, Frame, SizeHint};
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that emits keep-alive data frame whenever underlying body produces nothing for `interval`.
+    ///
+    ///By default keep-alive frame is empty, which only wakes consumer (e.g. to check its own state), as `hyper` does not send empty frames on the wire.
+    ///To keep connection alive against idle timeouts, use `with_payload` to emit data, that is ignored by peer (e.g. `:\n\n` comment for server-sent events).
+    ///
+    ///Timer is created using `F`, hence it is not tied to any particular runtime (e.g. `tokio::time::sleep` can be used).
+    #[project = KeepAliveBodyProj]
+    pub struct KeepAliveBody<T, F, S> {
+        #[pin]
+        body: T,
+        interval: Duration,
+        new_sleep: F,
+        sleep: Option<Pin<Box<S>>>,
+        payload: bytes::Bytes,
+    }
+}
+
+impl<T, F: FnMut(Duration) -> S, S: Future<Output = ()>> KeepAliveBody<T, F, S> {
+    #[inline(always)]
+    ///Creates new instance, emitting keep-alive after `interval` of inactivity
+    pub const fn new(body: T, interval: Duration, new_sleep: F) -> Self {
+        Self {
+            body,
+            interval,
+            new_sleep,
+            sleep: None,
+            payload: bytes::Bytes::new(),
+        }
+    }
+
+    #[inline(always)]
+    ///Sets `payload` to emit as keep-alive, instead of empty frame.
+    ///
+    ///As payload is part of body, it must be valid for content format, and body size becomes unknown.
+    pub fn with_payload(mut self, payload: bytes::Bytes) -> Self {
+        self.payload = payload;
+        self
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>, F: FnMut(Duration) -> S, S: Future<Output = ()>> HttpBody for KeepAliveBody<T, F, S> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let KeepAliveBodyProj { body, interval, new_sleep, sleep, payload } = self.project();
+
+        match HttpBody::poll_frame(body, cx) {
+            task::Poll::Pending => {
+                let timer = match sleep.as_mut() {
+                    Some(timer) => timer,
+                    None => sleep.insert(Box::pin((*new_sleep)(*interval))),
+                };
+                match Future::poll(timer.as_mut(), cx) {
+                    task::Poll::Ready(()) => {
+                        *sleep = None;
+                        task::Poll::Ready(Some(Ok(Frame::data(payload.clone()))))
+                    },
+                    task::Poll::Pending => task::Poll::Pending,
+                }
+            },
+            result => {
+                //Activity restarts idle timer
+                *sleep = None;
+                result
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        match self.payload.is_empty() {
+            true => self.body.size_hint(),
+            false => {
+                let mut hint = SizeHint::new();
+                hint.set_lower(self.body.size_hint().lower());
+                hint
+            },
+        }
+    }
+}
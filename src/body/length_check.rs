@@ -0,0 +1,120 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+
+///Name of trailer, indicating whether number of bytes sent matches declared length: `true`, `false` or `unknown`
+pub const LENGTH_MATCH: http::HeaderName = http::HeaderName::from_static("x-length-match");
+///Name of trailer, containing number of bytes actually sent
+pub const LENGTH_ACTUAL: http::HeaderName = http::HeaderName::from_static("x-length-actual");
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that counts passed through bytes and, once body ends, emits trailers comparing it with `SizeHint::exact()`.
+    ///
+    ///Declared length is taken from underlying body on creation, and is `unknown` if it has no exact size hint.
+    ///Trailers `X-Length-Match` and `X-Length-Actual` are added to body's own trailers, if any.
+    ///
+    ///Intended for diagnostic proxies, to surface upstream truncation in-band.
+    ///
+    ///Size hint reports only lower bound until trailers are emitted, as exact size makes `hyper` send `Content-Length` and drop trailers over `HTTP/1.1`.
+    #[project = LengthCheckBodyProj]
+    pub struct LengthCheckBody<T> {
+        #[pin]
+        body: T,
+        expected: Option<u64>,
+        actual: u64,
+        is_finished: bool,
+    }
+}
+
+impl<T: HttpBody> LengthCheckBody<T> {
+    #[inline]
+    ///Creates new instance, recording current exact size hint of `body` as declared length
+    pub fn new(body: T) -> Self {
+        Self {
+            expected: body.size_hint().exact(),
+            body,
+            actual: 0,
+            is_finished: false,
+        }
+    }
+}
+
+impl<T> LengthCheckBody<T> {
+    #[inline(always)]
+    ///Returns declared length, if known
+    pub const fn expected(&self) -> Option<u64> {
+        self.expected
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes passed through so far
+    pub const fn actual(&self) -> u64 {
+        self.actual
+    }
+}
+
+fn add_trailers(expected: Option<u64>, actual: u64, trailers: &mut http::HeaderMap) {
+    let is_match = match expected {
+        Some(expected) if expected == actual => "true",
+        Some(_) => "false",
+        None => "unknown",
+    };
+    trailers.insert(LENGTH_MATCH, http::HeaderValue::from_static(is_match));
+    trailers.insert(LENGTH_ACTUAL, actual.into());
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>> HttpBody for LengthCheckBody<T> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let LengthCheckBodyProj { body, expected, actual, is_finished } = self.project();
+
+        if *is_finished {
+            return task::Poll::Ready(None);
+        }
+
+        match HttpBody::poll_frame(body, cx) {
+            task::Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                Ok(data) => {
+                    *actual = actual.saturating_add(data.len() as u64);
+                    task::Poll::Ready(Some(Ok(Frame::data(data))))
+                },
+                Err(frame) => match frame.into_trailers() {
+                    Ok(mut trailers) => {
+                        *is_finished = true;
+                        add_trailers(*expected, *actual, &mut trailers);
+                        task::Poll::Ready(Some(Ok(Frame::trailers(trailers))))
+                    },
+                    Err(_) => unreach!(),
+                }
+            },
+            task::Poll::Ready(Some(Err(error))) => task::Poll::Ready(Some(Err(error))),
+            task::Poll::Ready(None) => {
+                *is_finished = true;
+                let mut trailers = http::HeaderMap::with_capacity(2);
+                add_trailers(*expected, *actual, &mut trailers);
+                task::Poll::Ready(Some(Ok(Frame::trailers(trailers))))
+            },
+            task::Poll::Pending => task::Poll::Pending,
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.is_finished
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        match self.is_finished {
+            false => {
+                let mut hint = SizeHint::new();
+                hint.set_lower(self.body.size_hint().lower());
+                hint
+            },
+            true => SizeHint::with_exact(0),
+        }
+    }
+}
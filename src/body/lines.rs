@@ -0,0 +1,308 @@
+use core::fmt;
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Collector};
+
+#[derive(Debug)]
+///Line validation error
+pub enum LineError<E> {
+    ///Underlying collector error
+    Inner(E),
+    ///Line exceeds allowed length
+    LineTooLong,
+    ///Body contains more lines than allowed
+    TooManyLines,
+}
+
+impl<E: fmt::Display> fmt::Display for LineError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inner(error) => fmt::Display::fmt(error, fmt),
+            Self::LineTooLong => fmt.write_str("LineTooLong"),
+            Self::TooManyLines => fmt.write_str("TooManyLines"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display + fmt::Debug> std::error::Error for LineError<E> {}
+
+#[derive(Debug)]
+///`LineStream` error
+pub enum LineStreamError<E> {
+    ///Underlying body error
+    Inner(E),
+    ///Line exceeds allowed length
+    LineTooLong,
+}
+
+impl<E: fmt::Display> fmt::Display for LineStreamError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inner(error) => fmt::Display::fmt(error, fmt),
+            Self::LineTooLong => fmt.write_str("LineTooLong"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display + fmt::Debug> std::error::Error for LineStreamError<E> {}
+
+///Collector wrapper, that limits length of every line (excluding `\n`) in body.
+///
+///Catches single pathologically long line early, even while total size is within limit.
+pub struct LineLengthCollector<C> {
+    inner: C,
+    max: usize,
+    current: usize,
+}
+
+impl<C> LineLengthCollector<C> {
+    #[inline(always)]
+    ///Creates new instance, allowing lines of at most `max` bytes.
+    pub const fn new(inner: C, max: usize) -> Self {
+        Self {
+            inner,
+            max,
+            current: 0,
+        }
+    }
+}
+
+impl<C: Collector> Collector for LineLengthCollector<C> {
+    type Output = C::Output;
+    type Error = LineError<C::Error>;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        let mut lines = data.split(|byte| *byte == b'\n');
+        //First piece continues line from previous frame
+        if let Some(line) = lines.next() {
+            self.current = self.current.saturating_add(line.len());
+        }
+        for line in lines {
+            if self.current > self.max {
+                break;
+            }
+            self.current = line.len();
+        }
+
+        match self.current > self.max {
+            true => Some(LineError::LineTooLong),
+            false => self.inner.append(data).map(LineError::Inner),
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, headers: http::HeaderMap) {
+        self.inner.on_trailers(headers)
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        self.current = 0;
+        self.inner.consume().map_err(LineError::Inner)
+    }
+}
+
+///Collector wrapper, that limits number of lines in body.
+///
+///Lines are counted by `\n` across frames, with last line counted even if it is not terminated.
+///Complements size limit with structural one, e.g. for small line oriented payloads.
+pub struct LineLimitCollector<C> {
+    inner: C,
+    max: usize,
+    lines: usize,
+    is_line_open: bool,
+}
+
+impl<C> LineLimitCollector<C> {
+    #[inline(always)]
+    ///Creates new instance, allowing at most `max` lines.
+    pub const fn new(inner: C, max: usize) -> Self {
+        Self {
+            inner,
+            max,
+            lines: 0,
+            is_line_open: false,
+        }
+    }
+}
+
+impl<C: Collector> Collector for LineLimitCollector<C> {
+    type Output = C::Output;
+    type Error = LineError<C::Error>;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        let mut lines = data.split(|byte| *byte == b'\n');
+        //First piece continues line from previous frame
+        if let Some(line) = lines.next() {
+            self.is_line_open = self.is_line_open || !line.is_empty();
+        }
+        for line in lines {
+            //Previous line is terminated
+            self.lines = self.lines.saturating_add(1);
+            self.is_line_open = !line.is_empty();
+        }
+
+        match self.lines + self.is_line_open as usize > self.max {
+            true => Some(LineError::TooManyLines),
+            false => self.inner.append(data).map(LineError::Inner),
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, headers: http::HeaderMap) {
+        self.inner.on_trailers(headers)
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        self.lines = 0;
+        self.is_line_open = false;
+        self.inner.consume().map_err(LineError::Inner)
+    }
+}
+
+pin_project_lite::pin_project! {
+    ///Stream of lines over `HttpBody`, split on `\n`, with line terminator (`\n` or `\r\n`) removed.
+    ///
+    ///Partial lines are buffered across frames, and last line is yielded even if it is not terminated.
+    ///Line is yielded without copy, if it is contained within single frame.
+    ///Trailers are ignored.
+    ///
+    ///Implements `futures_core::Stream` with `stream` feature, otherwise provides `poll_next` with the same contract.
+    #[project = LineStreamProj]
+    pub struct LineStream<T> {
+        #[pin]
+        body: T,
+        max: usize,
+        pending: bytes::Bytes,
+        partial: bytes::BytesMut,
+        is_finished: bool,
+    }
+}
+
+impl<T> LineStream<T> {
+    #[inline(always)]
+    ///Creates new instance, allowing lines of at most `max` bytes, excluding terminator.
+    pub fn new(body: T, max: usize) -> Self {
+        Self {
+            body,
+            max,
+            pending: bytes::Bytes::new(),
+            partial: bytes::BytesMut::new(),
+            is_finished: false,
+        }
+    }
+
+}
+
+//Returns `None` if line is too long
+fn trim_line(mut line: bytes::Bytes, max: usize) -> Option<bytes::Bytes> {
+    if line.ends_with(b"\n") {
+        line.truncate(line.len() - 1);
+        if line.ends_with(b"\r") {
+            line.truncate(line.len() - 1);
+        }
+    }
+
+    match line.len() > max {
+        true => None,
+        false => Some(line),
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>> LineStream<T> {
+    ///Polls for next line, returning `None` once body is exhausted.
+    ///
+    ///On error, stream is terminated.
+    pub fn poll_next(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Option<Result<bytes::Bytes, LineStreamError<T::Error>>>> {
+        let LineStreamProj { mut body, max, pending, partial, is_finished } = self.project();
+
+        loop {
+            let result = if let Some(pos) = pending.iter().position(|byte| *byte == b'\n') {
+                let line = pending.split_to(pos + 1);
+                let line = match partial.is_empty() {
+                    true => line,
+                    false => {
+                        partial.extend_from_slice(&line);
+                        partial.split().freeze()
+                    },
+                };
+                trim_line(line, *max)
+            } else {
+                partial.extend_from_slice(&pending);
+                pending.clear();
+                //Allow for `\r` which may be part of terminator
+                if partial.len() > max.saturating_add(1) {
+                    None
+                } else if *is_finished {
+                    match partial.is_empty() {
+                        true => break task::Poll::Ready(None),
+                        false => {
+                            let line = partial.split().freeze();
+                            trim_line(line, *max)
+                        }
+                    }
+                } else {
+                    match HttpBody::poll_frame(body.as_mut(), ctx) {
+                        task::Poll::Ready(Some(Ok(frame))) => if let Ok(data) = frame.into_data() {
+                            *pending = data;
+                        },
+                        task::Poll::Ready(Some(Err(error))) => {
+                            *is_finished = true;
+                            partial.clear();
+                            break task::Poll::Ready(Some(Err(LineStreamError::Inner(error))));
+                        },
+                        task::Poll::Ready(None) => *is_finished = true,
+                        task::Poll::Pending => break task::Poll::Pending,
+                    }
+                    continue;
+                }
+            };
+
+            break match result {
+                Some(line) => task::Poll::Ready(Some(Ok(line))),
+                None => {
+                    *is_finished = true;
+                    pending.clear();
+                    partial.clear();
+                    task::Poll::Ready(Some(Err(LineStreamError::LineTooLong)))
+                },
+            };
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<T: HttpBody<Data = bytes::Bytes>> futures_core::Stream for LineStream<T> {
+    type Item = Result<bytes::Bytes, LineStreamError<T::Error>>;
+
+    #[inline(always)]
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Option<Self::Item>> {
+        LineStream::poll_next(self, ctx)
+    }
+}
@@ -0,0 +1,465 @@
+//!Streaming decoder of `LZ4` frame format
+extern crate alloc;
+
+use alloc::vec::Vec;
+use std::io;
+
+use super::DecompressError;
+
+const MAGIC: u32 = 0x184D2204;
+//Skippable frames use magic numbers 0x184D2A50 to 0x184D2A5F
+const SKIPPABLE_MAGIC: u32 = 0x184D2A50;
+const SKIPPABLE_MASK: u32 = 0xFFFFFFF0;
+
+const FLAG_DICT_ID: u8 = 0x01;
+const FLAG_CONTENT_CHECKSUM: u8 = 0x04;
+const FLAG_CONTENT_SIZE: u8 = 0x08;
+const FLAG_BLOCK_CHECKSUM: u8 = 0x10;
+const FLAG_BLOCK_INDEPENDENCE: u8 = 0x20;
+const BLOCK_UNCOMPRESSED: u32 = 0x80000000;
+
+const MIN_MATCH: usize = 4;
+//Maximum distance of back reference
+const WINDOW_SIZE: usize = 64 * 1024;
+
+const PRIME1: u32 = 2654435761;
+const PRIME2: u32 = 2246822519;
+const PRIME3: u32 = 3266489917;
+const PRIME4: u32 = 668265263;
+const PRIME5: u32 = 374761393;
+
+#[inline(always)]
+const fn xxh32_round(acc: u32, lane: u32) -> u32 {
+    acc.wrapping_add(lane.wrapping_mul(PRIME2)).rotate_left(13).wrapping_mul(PRIME1)
+}
+
+#[inline(always)]
+fn read_u32(data: &[u8]) -> u32 {
+    u32::from_le_bytes([data[0], data[1], data[2], data[3]])
+}
+
+//Streaming XXH32 with seed 0, as used by LZ4 checksums
+struct Xxh32 {
+    lanes: [u32; 4],
+    //Incomplete stripe
+    buffer: [u8; 16],
+    buffered: usize,
+    len: u64,
+}
+
+impl Xxh32 {
+    const fn new() -> Self {
+        Self {
+            lanes: [PRIME1.wrapping_add(PRIME2), PRIME2, 0, 0u32.wrapping_sub(PRIME1)],
+            buffer: [0; 16],
+            buffered: 0,
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn stripe(lanes: &mut [u32; 4], stripe: &[u8]) {
+        for (idx, lane) in lanes.iter_mut().enumerate() {
+            *lane = xxh32_round(*lane, read_u32(&stripe[idx * 4..]));
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        self.len = self.len.wrapping_add(data.len() as u64);
+        if self.buffered > 0 {
+            let len = core::cmp::min(data.len(), 16 - self.buffered);
+            self.buffer[self.buffered..self.buffered + len].copy_from_slice(&data[..len]);
+            self.buffered += len;
+            data = &data[len..];
+            if self.buffered < 16 {
+                return;
+            }
+            Self::stripe(&mut self.lanes, &self.buffer);
+            self.buffered = 0;
+        }
+
+        let mut stripes = data.chunks_exact(16);
+        for stripe in stripes.by_ref() {
+            Self::stripe(&mut self.lanes, stripe);
+        }
+        let remainder = stripes.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+        self.buffered = remainder.len();
+    }
+
+    fn finish(&self) -> u32 {
+        let lanes = &self.lanes;
+        let mut acc = match self.len >= 16 {
+            true => lanes[0].rotate_left(1).wrapping_add(lanes[1].rotate_left(7)).wrapping_add(lanes[2].rotate_left(12)).wrapping_add(lanes[3].rotate_left(18)),
+            false => PRIME5,
+        };
+        acc = acc.wrapping_add(self.len as u32);
+
+        let mut words = self.buffer[..self.buffered].chunks_exact(4);
+        for word in words.by_ref() {
+            acc = acc.wrapping_add(read_u32(word).wrapping_mul(PRIME3)).rotate_left(17).wrapping_mul(PRIME4);
+        }
+        for byte in words.remainder() {
+            acc = acc.wrapping_add((*byte as u32).wrapping_mul(PRIME5)).rotate_left(11).wrapping_mul(PRIME1);
+        }
+
+        acc ^= acc >> 15;
+        acc = acc.wrapping_mul(PRIME2);
+        acc ^= acc >> 13;
+        acc = acc.wrapping_mul(PRIME3);
+        acc ^ (acc >> 16)
+    }
+}
+
+fn xxh32(data: &[u8]) -> u32 {
+    let mut hasher = Xxh32::new();
+    hasher.update(data);
+    hasher.finish()
+}
+
+enum Lz4Error {
+    //Input ended before frame element is complete
+    NeedInput,
+    Invalid(&'static str),
+    //Output exceeds limit
+    Overflow,
+}
+
+type Lz4Result<T> = Result<T, Lz4Error>;
+
+//Reads length, extended by following bytes when `base` is at its maximum
+fn read_length(data: &[u8], pos: &mut usize, base: usize, max: usize) -> Lz4Result<usize> {
+    let mut len = base;
+    if base == max {
+        loop {
+            let byte = match data.get(*pos) {
+                Some(byte) => *byte,
+                None => return Err(Lz4Error::Invalid("malformed block")),
+            };
+            *pos += 1;
+            len = match len.checked_add(byte as usize) {
+                Some(len) => len,
+                None => return Err(Lz4Error::Invalid("malformed block")),
+            };
+            if byte != u8::MAX {
+                break;
+            }
+        }
+    }
+    Ok(len)
+}
+
+#[inline(always)]
+fn check_block_size(size: usize, max_size: usize, limit: usize) -> Lz4Result<()> {
+    if size > max_size {
+        Err(Lz4Error::Invalid("block exceeds maximum size"))
+    } else if size > limit {
+        Err(Lz4Error::Overflow)
+    } else {
+        Ok(())
+    }
+}
+
+//Decodes complete block, with `window` being number of bytes of output, that can be referenced.
+//
+//Block output is limited by `limit`, checked before every copy.
+fn decode_block(data: &[u8], output: &mut Vec<u8>, window: usize, max_size: usize, limit: usize) -> Lz4Result<()> {
+    let block_start = output.len();
+    let mut pos = 0;
+    while pos < data.len() {
+        let token = data[pos];
+        pos += 1;
+
+        let literals = read_length(data, &mut pos, (token >> 4) as usize, 15)?;
+        let literals = match data.get(pos..pos.saturating_add(literals)) {
+            Some(literals) => literals,
+            None => return Err(Lz4Error::Invalid("malformed block")),
+        };
+        pos += literals.len();
+        check_block_size(output.len() - block_start + literals.len(), max_size, limit)?;
+        output.extend_from_slice(literals);
+
+        //Last sequence consists of literals only
+        if pos == data.len() {
+            break;
+        }
+
+        let offset = match data.get(pos..pos + 2) {
+            Some(offset) => u16::from_le_bytes([offset[0], offset[1]]) as usize,
+            None => return Err(Lz4Error::Invalid("malformed block")),
+        };
+        pos += 2;
+        if offset == 0 || offset > window + (output.len() - block_start) {
+            return Err(Lz4Error::Invalid("match offset is out of window"));
+        }
+
+        let len = read_length(data, &mut pos, (token & 0x0F) as usize, 15)?.saturating_add(MIN_MATCH);
+        check_block_size(output.len() - block_start + len, max_size, limit)?;
+        //Match may overlap with its own output, hence copied byte by byte
+        let start = output.len() - offset;
+        for idx in 0..len {
+            output.push(output[start + idx]);
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    Header,
+    //Remaining size of skippable frame
+    Skip(u32),
+    Blocks,
+    Trailer,
+}
+
+///Streaming `LZ4` frame decoder.
+///
+///Input may consist of multiple frames, including skippable ones, which are discarded as they arrive. Dictionaries are not supported.
+///
+///Output is kept until taken via `take_output`, with last `WINDOW_SIZE` bytes retained as window for linked blocks.
+///Blocks of frame with independence flag cannot reference previous blocks.
+///Block is decoded only once it is fully received.
+///
+///Total output is limited by `limit`, which is checked as block is being decoded, hence decoding stops before next block.
+pub(crate) struct Lz4Decoder {
+    input: Vec<u8>,
+    output: Vec<u8>,
+    //Start of output, that is not taken yet
+    taken: usize,
+    stage: Stage,
+    flags: u8,
+    max_block_size: usize,
+    content_size: Option<u64>,
+    //Checksum and size of current frame
+    checksum: Xxh32,
+    frame_len: u64,
+    frames: usize,
+    //Total output produced so far and its limit
+    produced: usize,
+    limit: usize,
+}
+
+impl Lz4Decoder {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Self {
+            input: Vec::new(),
+            output: Vec::new(),
+            taken: 0,
+            stage: Stage::Header,
+            flags: 0,
+            max_block_size: 0,
+            content_size: None,
+            checksum: Xxh32::new(),
+            frame_len: 0,
+            frames: 0,
+            produced: 0,
+            limit: usize::MAX,
+        }
+    }
+
+    #[inline(always)]
+    ///Sets limit of total output, exceeding which results in `DecompressError::Overflow`
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+
+    #[inline(always)]
+    pub(crate) fn get_ref(&self) -> &Vec<u8> {
+        &self.output
+    }
+
+    pub(crate) fn write_all(&mut self, data: &[u8]) -> Result<(), DecompressError> {
+        self.input.extend_from_slice(data);
+        self.decode()
+    }
+
+    ///Returns output produced since last call, retaining only window
+    pub(crate) fn take_output(&mut self) -> Vec<u8> {
+        let output = self.output[self.taken..].to_vec();
+        self.output.drain(..self.output.len().saturating_sub(WINDOW_SIZE));
+        self.taken = self.output.len();
+        output
+    }
+
+    ///Decodes remaining input, checking stream is complete
+    pub(crate) fn end(&mut self) -> Result<(), DecompressError> {
+        self.decode()?;
+        match self.stage == Stage::Header && self.input.is_empty() && self.frames > 0 {
+            true => Ok(()),
+            false => Err(DecompressError::Lz4(io::Error::new(io::ErrorKind::UnexpectedEof, "stream is incomplete"))),
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> Result<Vec<u8>, DecompressError> {
+        self.end()?;
+        self.output.drain(..self.taken);
+        Ok(self.output)
+    }
+
+    fn decode(&mut self) -> Result<(), DecompressError> {
+        match self.decode_inner() {
+            Ok(()) | Err(Lz4Error::NeedInput) => Ok(()),
+            Err(Lz4Error::Invalid(error)) => Err(DecompressError::Lz4(io::Error::new(io::ErrorKind::InvalidData, error))),
+            Err(Lz4Error::Overflow) => Err(DecompressError::Overflow),
+        }
+    }
+
+    fn header(&mut self) -> Lz4Result<usize> {
+        let input = &self.input;
+        let magic = match input.get(..4) {
+            Some(magic) => read_u32(magic),
+            None => return Err(Lz4Error::NeedInput),
+        };
+        if magic & SKIPPABLE_MASK == SKIPPABLE_MAGIC {
+            self.stage = match input.get(4..8) {
+                Some(len) => Stage::Skip(read_u32(len)),
+                None => return Err(Lz4Error::NeedInput),
+            };
+            return Ok(8);
+        } else if magic != MAGIC {
+            return Err(Lz4Error::Invalid("invalid magic number"));
+        }
+
+        let (flags, block) = match input.get(4..6) {
+            Some(descriptor) => (descriptor[0], descriptor[1]),
+            None => return Err(Lz4Error::NeedInput),
+        };
+        if flags >> 6 != 0b01 {
+            return Err(Lz4Error::Invalid("unsupported version"));
+        } else if flags & 0x02 != 0 || block & 0x8F != 0 {
+            return Err(Lz4Error::Invalid("reserved bits are set"));
+        } else if flags & FLAG_DICT_ID != 0 {
+            return Err(Lz4Error::Invalid("dictionary is not supported"));
+        }
+        let max_block_size = match block >> 4 {
+            4 => 64 * 1024,
+            5 => 256 * 1024,
+            6 => 1024 * 1024,
+            7 => 4 * 1024 * 1024,
+            _ => return Err(Lz4Error::Invalid("invalid block maximum size")),
+        };
+
+        let descriptor_end = match flags & FLAG_CONTENT_SIZE != 0 {
+            true => 14,
+            false => 6,
+        };
+        let checksum = match input.get(descriptor_end) {
+            Some(checksum) => *checksum,
+            None => return Err(Lz4Error::NeedInput),
+        };
+        if (xxh32(&input[4..descriptor_end]) >> 8) as u8 != checksum {
+            return Err(Lz4Error::Invalid("header checksum mismatch"));
+        }
+
+        self.content_size = match flags & FLAG_CONTENT_SIZE != 0 {
+            true => Some(u64::from_le_bytes([input[6], input[7], input[8], input[9], input[10], input[11], input[12], input[13]])),
+            false => None,
+        };
+        self.flags = flags;
+        self.max_block_size = max_block_size;
+        self.checksum = Xxh32::new();
+        self.frame_len = 0;
+        self.stage = Stage::Blocks;
+        Ok(descriptor_end + 1)
+    }
+
+    fn block(&mut self) -> Lz4Result<usize> {
+        let size = match self.input.get(..4) {
+            Some(size) => read_u32(size),
+            None => return Err(Lz4Error::NeedInput),
+        };
+        if size == 0 {
+            self.stage = Stage::Trailer;
+            return Ok(4);
+        }
+
+        let is_compressed = size & BLOCK_UNCOMPRESSED == 0;
+        let size = (size & !BLOCK_UNCOMPRESSED) as usize;
+        if size > self.max_block_size {
+            return Err(Lz4Error::Invalid("block exceeds maximum size"));
+        }
+        let checksum_len = match self.flags & FLAG_BLOCK_CHECKSUM != 0 {
+            true => 4,
+            false => 0,
+        };
+        let block = match self.input.get(4..4 + size + checksum_len) {
+            Some(block) => block,
+            None => return Err(Lz4Error::NeedInput),
+        };
+        let (block, checksum) = block.split_at(size);
+        if !checksum.is_empty() && read_u32(checksum) != xxh32(block) {
+            return Err(Lz4Error::Invalid("block checksum mismatch"));
+        }
+
+        let output_len = self.output.len();
+        let limit = self.limit.saturating_sub(self.produced);
+        match is_compressed {
+            true => {
+                //Only current frame can be referenced, and only current block if blocks are independent
+                let window = match self.flags & FLAG_BLOCK_INDEPENDENCE != 0 {
+                    true => 0,
+                    false => core::cmp::min(output_len as u64, self.frame_len) as usize,
+                };
+                if let Err(error) = decode_block(block, &mut self.output, window, self.max_block_size, limit) {
+                    self.output.truncate(output_len);
+                    return Err(error);
+                }
+            },
+            false => {
+                check_block_size(block.len(), self.max_block_size, limit)?;
+                self.output.extend_from_slice(block);
+            },
+        }
+        let block = &self.output[output_len..];
+        self.produced = self.produced.saturating_add(block.len());
+        self.checksum.update(block);
+        self.frame_len = self.frame_len.saturating_add(block.len() as u64);
+        Ok(4 + size + checksum_len)
+    }
+
+    fn trailer(&mut self) -> Lz4Result<usize> {
+        if let Some(content_size) = self.content_size {
+            if content_size != self.frame_len {
+                return Err(Lz4Error::Invalid("content size mismatch"));
+            }
+        }
+
+        let len = match self.flags & FLAG_CONTENT_CHECKSUM != 0 {
+            true => match self.input.get(..4) {
+                Some(checksum) if read_u32(checksum) == self.checksum.finish() => 4,
+                Some(_) => return Err(Lz4Error::Invalid("content checksum mismatch")),
+                None => return Err(Lz4Error::NeedInput),
+            },
+            false => 0,
+        };
+        self.frames += 1;
+        self.stage = Stage::Header;
+        Ok(len)
+    }
+
+    fn decode_inner(&mut self) -> Lz4Result<()> {
+        loop {
+            let len = match self.stage {
+                Stage::Header if self.input.is_empty() => break Ok(()),
+                Stage::Header => self.header()?,
+                Stage::Skip(0) => {
+                    self.frames += 1;
+                    self.stage = Stage::Header;
+                    0
+                },
+                Stage::Skip(_) if self.input.is_empty() => break Err(Lz4Error::NeedInput),
+                Stage::Skip(remaining) => {
+                    let len = core::cmp::min(remaining as usize, self.input.len());
+                    self.stage = Stage::Skip(remaining - len as u32);
+                    len
+                },
+                Stage::Blocks => self.block()?,
+                Stage::Trailer => self.trailer()?,
+            };
+            self.input.drain(..len);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that transforms every data frame using `F`, passing trailers as it is.
+    ///
+    ///As mapping may change length of data, size hint is unknown.
+    #[project = MapBodyProj]
+    pub struct MapBody<T, F> {
+        #[pin]
+        body: T,
+        map: F,
+    }
+}
+
+impl<T, F: FnMut(bytes::Bytes) -> bytes::Bytes> MapBody<T, F> {
+    #[inline(always)]
+    ///Creates new instance, applying `map` to every data frame of `body`
+    pub const fn new(body: T, map: F) -> Self {
+        Self {
+            body,
+            map,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns underlying body and mapping function
+    pub fn into_parts(self) -> (T, F) {
+        (self.body, self.map)
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>, F: FnMut(bytes::Bytes) -> bytes::Bytes> HttpBody for MapBody<T, F> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let MapBodyProj { body, map } = self.project();
+
+        match HttpBody::poll_frame(body, cx) {
+            task::Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                Ok(data) => task::Poll::Ready(Some(Ok(Frame::data((*map)(data))))),
+                Err(frame) => task::Poll::Ready(Some(Ok(frame))),
+            },
+            result => result,
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::new()
+    }
+}
@@ -0,0 +1,71 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task;
+use core::time::Duration;
+use std::time::Instant;
+
+use super::{HttpBody, Collect, Collector, CollectError};
+
+///`Collect` wrapper, that enforces minimal throughput of body.
+///
+///Once `grace` period passes since first poll, average throughput over whole elapsed time must not drop below `min_rate` bytes per second,
+///otherwise `CollectError::TooSlow` is returned.
+///
+///Note that throughput is checked only when future is polled, so body that stalls forever without waking is not detected.
+///Combine it with `CollectTimeout` to handle such peers.
+pub struct CollectMinRate<const S: usize, T, C> {
+    collect: Collect<S, T, C>,
+    min_rate: u64,
+    grace: Duration,
+    started: Option<Instant>,
+}
+
+pin_invariants!(impl[const S: usize, T, C] CollectMinRate<S, T, C> where Collect<S, T, C>: Unpin);
+
+impl<T, C, const S: usize> CollectMinRate<S, T, C> {
+    #[inline(always)]
+    ///Creates new instance, requiring at least `min_rate` bytes per second after `grace` period.
+    pub const fn new(collect: Collect<S, T, C>, min_rate: u64, grace: Duration) -> Self {
+        Self {
+            collect,
+            min_rate,
+            grace,
+            started: None,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns underlying future
+    pub fn into_inner(self) -> Collect<S, T, C> {
+        self.collect
+    }
+}
+
+impl<E, D: bytes::Buf, T: HttpBody<Data = D, Error = E>, C: Collector, const S: usize> Future for CollectMinRate<S, T, C> {
+    type Output = Result<C::Output, CollectError<E, C::Error, C::Output>>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        project!(self => CollectMinRate { #[pin] collect, min_rate, grace, started });
+        let started = *started.get_or_insert_with(Instant::now);
+
+        match Future::poll(collect.as_mut(), ctx) {
+            task::Poll::Ready(result) => task::Poll::Ready(result),
+            task::Poll::Pending => {
+                let elapsed = started.elapsed();
+                if elapsed <= *grace {
+                    return task::Poll::Pending;
+                }
+
+                let received = collect.received();
+                let required = (*min_rate as u128).saturating_mul(elapsed.as_millis()) / 1000;
+                match (received as u128) < required {
+                    true => task::Poll::Ready(Err(CollectError::TooSlow {
+                        received,
+                        elapsed,
+                    })),
+                    false => task::Poll::Pending,
+                }
+            }
+        }
+    }
+}
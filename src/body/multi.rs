@@ -0,0 +1,94 @@
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use core::pin::Pin;
+use core::{task, fmt};
+
+use super::{HttpBody, Frame, SizeHint};
+
+#[derive(Clone, Default)]
+///HTTP body, consisting of multiple chunks, each emitted as separate frame.
+///
+///Suitable to build multi-frame body incrementally before serving it.
+pub struct MultiBody {
+    chunks: VecDeque<bytes::Bytes>,
+}
+
+impl MultiBody {
+    #[inline(always)]
+    ///Creates empty body
+    pub const fn new() -> Self {
+        Self {
+            chunks: VecDeque::new(),
+        }
+    }
+
+    #[inline]
+    ///Adds chunk to the end of body.
+    ///
+    ///Empty chunks are ignored.
+    pub fn append(&mut self, chunk: bytes::Bytes) {
+        if !chunk.is_empty() {
+            self.chunks.push_back(chunk);
+        }
+    }
+
+    #[inline]
+    ///Adds chunk to the start of body.
+    ///
+    ///Empty chunks are ignored.
+    pub fn prepend(&mut self, chunk: bytes::Bytes) {
+        if !chunk.is_empty() {
+            self.chunks.push_front(chunk);
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of remaining chunks
+    pub fn chunks_len(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+impl From<alloc::vec::Vec<bytes::Bytes>> for MultiBody {
+    #[inline]
+    fn from(chunks: alloc::vec::Vec<bytes::Bytes>) -> Self {
+        chunks.into_iter().collect()
+    }
+}
+
+impl FromIterator<bytes::Bytes> for MultiBody {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = bytes::Bytes>>(chunks: I) -> Self {
+        Self {
+            chunks: chunks.into_iter().filter(|chunk| !chunk.is_empty()).collect(),
+        }
+    }
+}
+
+impl HttpBody for MultiBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn poll_frame(mut self: Pin<&mut Self>, _cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        task::Poll::Ready(self.chunks.pop_front().map(|chunk| Ok(Frame::data(chunk))))
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.chunks.iter().map(|chunk| chunk.len() as u64).sum())
+    }
+}
+
+impl fmt::Debug for MultiBody {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("MultiBody").field("chunks", &self.chunks.len()).finish()
+    }
+}
@@ -0,0 +1,242 @@
+extern crate alloc;
+
+use alloc::string::String;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::{fmt, task};
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+
+use super::{HttpBody, Frame, MultiBody, SizeHint};
+
+static BOUNDARY_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+fn generate_boundary(seed: usize) -> String {
+    let counter = BOUNDARY_COUNTER.fetch_add(1, Ordering::Relaxed) as u64;
+    let seed = (seed as u64).rotate_left(32);
+    //RandomState is seeded from OS randomness, hence boundary cannot be guessed in advance
+    #[cfg(feature = "std")]
+    let seed = seed ^ std::collections::hash_map::RandomState::new().hash_one(counter);
+    //splitmix64 over counter and seed, to make boundaries distinct across bodies and processes
+    let mut state = counter ^ seed;
+    let mut boundary = String::with_capacity(48);
+    boundary.push_str("----http-fancy-");
+    for _ in 0..2 {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut value = state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^= value >> 31;
+        let _ = write!(&mut boundary, "{:016x}", value);
+    }
+    boundary
+}
+
+//Escapes quoted parameter value as per HTML form submission rules
+fn push_quoted(out: &mut String, value: &str) {
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("%22"),
+            '\r' => out.push_str("%0D"),
+            '\n' => out.push_str("%0A"),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///`MultipartBody` error
+pub enum MultipartError {
+    ///Boundary is not valid as per RFC 2046: it must be 1 to 70 characters, not ending with space
+    InvalidBoundary,
+    ///Part content type is not valid header value
+    InvalidContentType,
+    ///Part content contains boundary delimiter, which would allow to forge parts
+    BoundaryInContent,
+    ///Body is already being emitted, so no more parts can be added
+    Finished,
+}
+
+impl fmt::Display for MultipartError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidBoundary => fmt.write_str("Multipart boundary is invalid"),
+            Self::InvalidContentType => fmt.write_str("Multipart part content type is invalid"),
+            Self::BoundaryInContent => fmt.write_str("Multipart part content contains boundary"),
+            Self::Finished => fmt.write_str("Multipart body is already emitted"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MultipartError {}
+
+#[inline]
+//bchars as per RFC 2046
+fn is_boundary_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || b"'()+_,-./:=? ".contains(&byte)
+}
+
+#[inline]
+//bchars that are not valid in token, requiring parameter to be quoted
+fn is_boundary_quoted(byte: u8) -> bool {
+    b"(),/:=? ".contains(&byte)
+}
+
+///`multipart/form-data` HTTP body builder.
+///
+///Parts are emitted in order of addition, with part content being emitted as separate frame without copy.
+///Exact length is known upfront, so `Content-Length` can be set.
+///
+///Use `content_type` to get value of `Content-Type` header, containing boundary.
+///Part content containing boundary delimiter is rejected, as it would allow to forge parts.
+pub struct MultipartBody {
+    boundary: String,
+    body: MultiBody,
+    is_finished: bool,
+}
+
+impl MultipartBody {
+    #[inline]
+    ///Creates new instance with generated boundary
+    pub fn new() -> Self {
+        let seed = &BOUNDARY_COUNTER as *const _ as usize;
+        Self {
+            boundary: generate_boundary(seed),
+            body: MultiBody::new(),
+            is_finished: false,
+        }
+    }
+
+    ///Creates new instance with specified `boundary`.
+    ///
+    ///Returns error if boundary is not valid as per RFC 2046.
+    pub fn with_boundary(boundary: String) -> Result<Self, MultipartError> {
+        let bytes = boundary.as_bytes();
+        match bytes.len() {
+            1..=70 if bytes.iter().all(|byte| is_boundary_char(*byte)) && !bytes.ends_with(b" ") => Ok(Self {
+                boundary,
+                body: MultiBody::new(),
+                is_finished: false,
+            }),
+            _ => Err(MultipartError::InvalidBoundary),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns boundary
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    #[inline]
+    ///Returns `Content-Type` header value
+    pub fn content_type(&self) -> http::HeaderValue {
+        let value = match self.boundary.bytes().any(is_boundary_quoted) {
+            true => alloc::format!("multipart/form-data; boundary=\"{}\"", self.boundary),
+            false => alloc::format!("multipart/form-data; boundary={}", self.boundary),
+        };
+        match http::HeaderValue::from_str(&value) {
+            Ok(value) => value,
+            Err(_) => unreach!(),
+        }
+    }
+
+    fn add_part(&mut self, name: &str, filename: Option<&str>, content_type: Option<&str>, content: bytes::Bytes) -> Result<&mut Self, MultipartError> {
+        if self.is_finished {
+            return Err(MultipartError::Finished);
+        }
+        //Header value cannot contain CR or LF, which would allow to inject headers or boundary
+        if let Some(content_type) = content_type {
+            if http::HeaderValue::from_str(content_type).is_err() {
+                return Err(MultipartError::InvalidContentType);
+            }
+        }
+        //Delimiter is `--` followed by boundary
+        let boundary = self.boundary.as_bytes();
+        if content.windows(boundary.len() + 2).any(|window| window.starts_with(b"--") && &window[2..] == boundary) {
+            return Err(MultipartError::BoundaryInContent);
+        }
+
+        let mut headers = String::new();
+        headers.push_str("--");
+        headers.push_str(&self.boundary);
+        headers.push_str("\r\nContent-Disposition: form-data; name=");
+        push_quoted(&mut headers, name);
+        if let Some(filename) = filename {
+            headers.push_str("; filename=");
+            push_quoted(&mut headers, filename);
+        }
+        if let Some(content_type) = content_type {
+            headers.push_str("\r\nContent-Type: ");
+            headers.push_str(content_type);
+        }
+        headers.push_str("\r\n\r\n");
+
+        self.body.append(headers.into());
+        self.body.append(content);
+        self.body.append(bytes::Bytes::from_static(b"\r\n"));
+        Ok(self)
+    }
+
+    #[inline]
+    ///Adds text field
+    ///
+    ///Returns error if body is already being emitted or `value` contains boundary delimiter.
+    pub fn add_field(&mut self, name: &str, value: &str) -> Result<&mut Self, MultipartError> {
+        self.add_part(name, None, None, bytes::Bytes::copy_from_slice(value.as_bytes()))
+    }
+
+    #[inline]
+    ///Adds file with specified `filename` and `content_type`
+    ///
+    ///Returns error if body is already being emitted, `content_type` is not valid header value or `content` contains boundary delimiter.
+    pub fn add_file(&mut self, name: &str, filename: &str, content_type: &str, content: bytes::Bytes) -> Result<&mut Self, MultipartError> {
+        self.add_part(name, Some(filename), Some(content_type), content)
+    }
+
+    #[inline(always)]
+    fn closing_len(&self) -> u64 {
+        //--boundary--\r\n
+        self.boundary.len() as u64 + 6
+    }
+}
+
+impl Default for MultipartBody {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpBody for MultipartBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        if !self.is_finished {
+            self.is_finished = true;
+            let closing = alloc::format!("--{}--\r\n", self.boundary);
+            self.body.append(closing.into());
+        }
+        HttpBody::poll_frame(Pin::new(&mut self.body), cx)
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.is_finished && self.body.is_end_stream()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        let len = self.body.size_hint().exact().unwrap_or(0);
+        match self.is_finished {
+            true => SizeHint::with_exact(len),
+            false => SizeHint::with_exact(len + self.closing_len()),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::mem;
+
+use super::Collector;
+
+///Collector, that returns collected body alongside with offsets at which each frame started.
+///
+///Output is `(data, offsets)`, where `data[offsets[idx]..offsets[idx + 1]]` is content of frame `idx`.
+///Note that `Collect` skips empty frames, hence they have no offset.
+pub struct FrameOffsetsCollector {
+    buffer: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl FrameOffsetsCollector {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            offsets: Vec::new(),
+        }
+    }
+}
+
+impl Default for FrameOffsetsCollector {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for FrameOffsetsCollector {
+    type Output = (Vec<u8>, Vec<usize>);
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.offsets.push(self.buffer.len());
+        self.buffer.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        Ok((mem::take(&mut self.buffer), mem::take(&mut self.offsets)))
+    }
+}
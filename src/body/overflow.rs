@@ -0,0 +1,138 @@
+use core::fmt;
+
+use super::Collector;
+
+#[derive(Debug)]
+///`OverflowPolicy` error
+pub enum OverflowPolicyError<E> {
+    ///Underlying collector error
+    Inner(E),
+    ///Threshold is exceeded
+    Overflow,
+}
+
+impl<E: fmt::Display> fmt::Display for OverflowPolicyError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inner(error) => fmt::Display::fmt(error, fmt),
+            Self::Overflow => fmt.write_str("Overflow"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display + fmt::Debug> std::error::Error for OverflowPolicyError<E> {}
+
+enum OverflowAction<F> {
+    Error,
+    Truncate,
+    Callback(F),
+}
+
+///Collector wrapper, that applies configurable action once collected size exceeds threshold.
+///
+///Possible actions:
+///- Error - fails with `OverflowPolicyError::Overflow`, same as `Collect` limit.
+///- Truncate - stops appending data, but keeps draining body. `len` never exceeds threshold,
+///so `Collect` limit is not triggered as long as it is above threshold plus size of largest frame (as `Collect` checks limit before appending frame).
+///- Callback - invokes callback once with collected size, and continues collecting. `Collect` limit still applies.
+pub struct OverflowPolicy<C, F = fn(usize)> {
+    inner: C,
+    threshold: usize,
+    action: OverflowAction<F>,
+    is_overflown: bool,
+}
+
+impl<C> OverflowPolicy<C> {
+    #[inline(always)]
+    ///Creates new instance, failing once `threshold` is exceeded
+    pub const fn error(inner: C, threshold: usize) -> Self {
+        Self {
+            inner,
+            threshold,
+            action: OverflowAction::Error,
+            is_overflown: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Creates new instance, discarding data past `threshold`
+    pub const fn truncate(inner: C, threshold: usize) -> Self {
+        Self {
+            inner,
+            threshold,
+            action: OverflowAction::Truncate,
+            is_overflown: false,
+        }
+    }
+}
+
+impl<C, F: FnMut(usize)> OverflowPolicy<C, F> {
+    #[inline(always)]
+    ///Creates new instance, invoking `callback` once `threshold` is exceeded
+    pub const fn callback(inner: C, threshold: usize, callback: F) -> Self {
+        Self {
+            inner,
+            threshold,
+            action: OverflowAction::Callback(callback),
+            is_overflown: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns whether threshold has been exceeded by body being collected.
+    ///
+    ///Reset on `consume`, so that re-used collector applies action to next body as well.
+    pub const fn is_overflown(&self) -> bool {
+        self.is_overflown
+    }
+}
+
+impl<C: Collector, F: FnMut(usize) + Unpin> Collector for OverflowPolicy<C, F> {
+    type Output = C::Output;
+    type Error = OverflowPolicyError<C::Error>;
+
+    fn append(&mut self, mut data: bytes::Bytes) -> Option<Self::Error> {
+        let len = self.inner.len().saturating_add(data.len());
+        if len > self.threshold {
+            let is_first = !self.is_overflown;
+            self.is_overflown = true;
+            match &mut self.action {
+                OverflowAction::Error => return Some(OverflowPolicyError::Overflow),
+                OverflowAction::Truncate => {
+                    data.truncate(self.threshold.saturating_sub(self.inner.len()));
+                    if data.is_empty() {
+                        return None;
+                    }
+                },
+                OverflowAction::Callback(callback) => if is_first {
+                    callback(len);
+                },
+            }
+        }
+
+        self.inner.append(data).map(OverflowPolicyError::Inner)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, headers: http::HeaderMap) {
+        self.inner.on_trailers(headers)
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        self.is_overflown = false;
+        self.inner.consume().map_err(OverflowPolicyError::Inner)
+    }
+}
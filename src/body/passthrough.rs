@@ -0,0 +1,127 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+
+///Hook, invoked by `PassthroughBody` for every frame passing through.
+///
+///Both methods have no-op default implementation, so only needed behavior should be implemented.
+pub trait FrameHook {
+    #[inline(always)]
+    ///Called on every frame with its zero based `index`, returning frame to emit.
+    fn on_frame(&mut self, index: u64, frame: Frame<bytes::Bytes>) -> Frame<bytes::Bytes> {
+        let _ = index;
+        frame
+    }
+
+    #[inline(always)]
+    ///Called once, when underlying body successfully ends, with total number of frames emitted.
+    fn on_end(&mut self, frames: u64) {
+        let _ = frames;
+    }
+}
+
+impl FrameHook for () {
+}
+
+impl<F: FnMut(u64, Frame<bytes::Bytes>) -> Frame<bytes::Bytes>> FrameHook for F {
+    #[inline(always)]
+    fn on_frame(&mut self, index: u64, frame: Frame<bytes::Bytes>) -> Frame<bytes::Bytes> {
+        (self)(index, frame)
+    }
+}
+
+pin_project_lite::pin_project! {
+    ///Transparent HTTP body wrapper, emitting exactly frames of underlying body, preserving their boundaries.
+    ///
+    ///Serves as base for middleware bodies: implement `FrameHook` instead of re-implementing poll delegation.
+    ///Without hook (i.e. `()`) it only counts frames.
+    #[project = PassthroughBodyProj]
+    pub struct PassthroughBody<T, H = ()> {
+        #[pin]
+        body: T,
+        hook: H,
+        frames: u64,
+        is_finished: bool,
+    }
+}
+
+impl<T> PassthroughBody<T> {
+    #[inline(always)]
+    ///Creates new instance without hook
+    pub const fn new(body: T) -> Self {
+        Self::with_hook(body, ())
+    }
+}
+
+impl<T, H> PassthroughBody<T, H> {
+    #[inline(always)]
+    ///Creates new instance, invoking `hook` on every frame of `body`
+    pub const fn with_hook(body: T, hook: H) -> Self {
+        Self {
+            body,
+            hook,
+            frames: 0,
+            is_finished: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of frames emitted so far
+    pub const fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    #[inline(always)]
+    ///Returns reference to underlying body
+    pub const fn get_ref(&self) -> &T {
+        &self.body
+    }
+
+    #[inline(always)]
+    ///Returns reference to hook
+    pub const fn hook(&self) -> &H {
+        &self.hook
+    }
+
+    #[inline(always)]
+    ///Returns underlying body and hook
+    pub fn into_parts(self) -> (T, H) {
+        (self.body, self.hook)
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>, H: FrameHook> HttpBody for PassthroughBody<T, H> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let PassthroughBodyProj { body, hook, frames, is_finished } = self.project();
+
+        match HttpBody::poll_frame(body, cx) {
+            task::Poll::Ready(Some(Ok(frame))) => {
+                let frame = hook.on_frame(*frames, frame);
+                *frames = frames.saturating_add(1);
+                task::Poll::Ready(Some(Ok(frame)))
+            },
+            task::Poll::Ready(None) => {
+                if !*is_finished {
+                    *is_finished = true;
+                    hook.on_end(*frames);
+                }
+                task::Poll::Ready(None)
+            },
+            result => result,
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
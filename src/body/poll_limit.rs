@@ -0,0 +1,94 @@
+use core::pin::Pin;
+use core::{task, fmt};
+
+use super::{HttpBody, Frame, SizeHint};
+
+///Possible errors from `PollLimitBody`
+#[derive(Debug)]
+pub enum PollLimitError<E> {
+    ///Underlying body error
+    Inner(E),
+    ///Body is polled more than allowed
+    PollLimitExceeded,
+}
+
+impl<E: fmt::Display> fmt::Display for PollLimitError<E> {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inner(error) => fmt::Display::fmt(error, fmt),
+            Self::PollLimitExceeded => fmt.write_str("PollLimitExceeded"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Display + fmt::Debug> std::error::Error for PollLimitError<E> {}
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, limiting total number of `poll_frame` invocations.
+    ///
+    ///Protects against bodies that never make progress: once `max` polls are made, every following poll returns `PollLimitError::PollLimitExceeded`.
+    #[project = PollLimitBodyProj]
+    pub struct PollLimitBody<T> {
+        #[pin]
+        body: T,
+        polls: usize,
+        max: usize,
+    }
+}
+
+impl<T> PollLimitBody<T> {
+    #[inline(always)]
+    ///Creates new instance, allowing at most `max` polls of `body`
+    pub const fn new(body: T, max: usize) -> Self {
+        Self {
+            body,
+            polls: 0,
+            max,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of polls made so far
+    pub const fn polls(&self) -> usize {
+        self.polls
+    }
+
+    #[inline(always)]
+    ///Returns underlying body
+    pub fn into_inner(self) -> T {
+        self.body
+    }
+}
+
+impl<T: HttpBody> HttpBody for PollLimitBody<T> {
+    type Data = T::Data;
+    type Error = PollLimitError<T::Error>;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let PollLimitBodyProj { body, polls, max } = self.project();
+
+        if *polls >= *max {
+            return task::Poll::Ready(Some(Err(PollLimitError::PollLimitExceeded)));
+        }
+        *polls += 1;
+
+        match HttpBody::poll_frame(body, cx) {
+            task::Poll::Ready(Some(Ok(frame))) => task::Poll::Ready(Some(Ok(frame))),
+            task::Poll::Ready(Some(Err(error))) => task::Poll::Ready(Some(Err(PollLimitError::Inner(error)))),
+            task::Poll::Ready(None) => task::Poll::Ready(None),
+            task::Poll::Pending => task::Poll::Pending,
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
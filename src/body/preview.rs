@@ -0,0 +1,70 @@
+use core::{cmp, mem};
+
+use super::{Collect, Collector};
+
+///Collector, that returns preview of first `P` bytes alongside full body.
+///
+///Output is `(preview, full)`, where `preview` is zero-copy slice of `full`, and is shorter than `P` only if whole body is.
+pub struct PreviewCollector<const P: usize> {
+    buffer: bytes::BytesMut,
+}
+
+impl<const P: usize> PreviewCollector<P> {
+    #[inline(always)]
+    ///Creates new instance
+    pub fn new() -> Self {
+        Self {
+            buffer: bytes::BytesMut::new(),
+        }
+    }
+}
+
+impl<const P: usize> Default for PreviewCollector<P> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const P: usize> Collector for PreviewCollector<P> {
+    type Output = (bytes::Bytes, bytes::Bytes);
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.buffer.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        let full = mem::take(&mut self.buffer).freeze();
+        let preview = full.slice(..cmp::min(P, full.len()));
+        Ok((preview, full))
+    }
+}
+
+///Future that collects `HttpBody` with size limit `S`, returning preview of first `P` bytes alongside full body
+pub type PreviewCollect<const P: usize, const S: usize, T> = Collect<S, T, PreviewCollector<P>>;
+
+impl<T, const P: usize, const S: usize> Collect<S, T, PreviewCollector<P>> {
+    #[inline(always)]
+    ///Creates new instance, returning preview alongside full body
+    pub fn preview(body: T) -> Self {
+        Self::new(body, PreviewCollector::new())
+    }
+}
@@ -0,0 +1,113 @@
+extern crate alloc;
+
+use core::fmt;
+
+use super::Body;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Satisfied byte range, formatted as `Content-Range` header value `bytes <start>-<end>/<len>`
+pub struct ContentRange {
+    start: u64,
+    end: u64,
+    len: u64,
+}
+
+impl ContentRange {
+    #[inline(always)]
+    ///Returns first byte position
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
+
+    #[inline(always)]
+    ///Returns last byte position, inclusive
+    pub const fn end(&self) -> u64 {
+        self.end
+    }
+
+    #[inline(always)]
+    ///Returns complete length of representation
+    pub const fn complete_len(&self) -> u64 {
+        self.len
+    }
+
+    #[inline]
+    ///Returns `Content-Range` header value
+    pub fn to_header_value(&self) -> http::HeaderValue {
+        let value = alloc::format!("{}", self);
+        match http::HeaderValue::from_str(&value) {
+            Ok(value) => value,
+            Err(_) => unreach!(),
+        }
+    }
+}
+
+impl fmt::Display for ContentRange {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_fmt(format_args!("bytes {}-{}/{}", self.start, self.end, self.len))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Range cannot be satisfied, to be responded with `416 Range Not Satisfiable`
+pub struct RangeError {
+    len: u64,
+}
+
+impl RangeError {
+    #[inline(always)]
+    ///Returns complete length of representation
+    pub const fn complete_len(&self) -> u64 {
+        self.len
+    }
+
+    #[inline]
+    ///Returns `Content-Range` header value `bytes */<len>`, to be set on `416` response
+    pub fn to_header_value(&self) -> http::HeaderValue {
+        let value = alloc::format!("bytes */{}", self.len);
+        match http::HeaderValue::from_str(&value) {
+            Ok(value) => value,
+            Err(_) => unreach!(),
+        }
+    }
+}
+
+impl fmt::Display for RangeError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_fmt(format_args!("RangeNotSatisfiable(length {})", self.len))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RangeError {}
+
+impl Body {
+    ///Slices body to byte range `start..=end`, returning it alongside with `Content-Range` to set.
+    ///
+    ///As per RFC 9110, `end` beyond body is clamped to its last byte.
+    ///Range is not satisfiable if `start` is beyond body or greater than `end`.
+    ///
+    ///Slicing is zero-copy.
+    pub fn range(self, start: u64, end: u64) -> Result<(Self, ContentRange), RangeError> {
+        let len = self.inner.len() as u64;
+        if start >= len || start > end {
+            return Err(RangeError {
+                len,
+            });
+        }
+
+        let end = core::cmp::min(end, len - 1);
+        let range = ContentRange {
+            start,
+            end,
+            len,
+        };
+        let body = Self {
+            inner: self.inner.slice(start as usize..=end as usize),
+            trailers: self.trailers,
+        };
+        Ok((body, range))
+    }
+}
@@ -0,0 +1,98 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that re-emits data in frames of fixed size, splitting large frames and coalescing small ones.
+    ///
+    ///Last frame contains remainder of body and may be smaller. Trailers are emitted after all data.
+    ///
+    ///Useful for predictable frame sizing in tests and rate control.
+    #[project = RechunkBodyProj]
+    pub struct RechunkBody<T> {
+        #[pin]
+        body: T,
+        size: usize,
+        buffer: bytes::BytesMut,
+        trailers: Option<http::HeaderMap>,
+        is_finished: bool,
+    }
+}
+
+impl<T> RechunkBody<T> {
+    #[inline(always)]
+    ///Creates new instance, emitting frames of `size` bytes.
+    ///
+    ///Panics if `size` is zero.
+    pub fn new(body: T, size: usize) -> Self {
+        assert!(size > 0, "Frame size cannot be zero");
+        Self {
+            body,
+            size,
+            buffer: bytes::BytesMut::new(),
+            trailers: None,
+            is_finished: false,
+        }
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>> HttpBody for RechunkBody<T> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let RechunkBodyProj { mut body, size, buffer, trailers, is_finished } = self.project();
+
+        loop {
+            if buffer.len() >= *size {
+                break task::Poll::Ready(Some(Ok(Frame::data(buffer.split_to(*size).freeze()))));
+            } else if *is_finished {
+                break match buffer.is_empty() {
+                    true => task::Poll::Ready(trailers.take().map(|trailers| Ok(Frame::trailers(trailers)))),
+                    false => task::Poll::Ready(Some(Ok(Frame::data(buffer.split().freeze())))),
+                };
+            }
+
+            match HttpBody::poll_frame(body.as_mut(), cx) {
+                task::Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                    Ok(data) => buffer.extend_from_slice(&data),
+                    Err(frame) => if let Ok(headers) = frame.into_trailers() {
+                        match trailers.as_mut() {
+                            Some(existing) => existing.extend(headers),
+                            None => *trailers = Some(headers),
+                        }
+                    },
+                },
+                task::Poll::Ready(Some(Err(error))) => break task::Poll::Ready(Some(Err(error))),
+                task::Poll::Ready(None) => *is_finished = true,
+                task::Poll::Pending => break task::Poll::Pending,
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.buffer.is_empty() && self.trailers.is_none() && (self.is_finished || self.body.is_end_stream())
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let buffered = self.buffer.len() as u64;
+        if self.is_finished {
+            return SizeHint::with_exact(buffered);
+        }
+
+        let inner = self.body.size_hint();
+        match inner.exact() {
+            Some(exact) => SizeHint::with_exact(exact.saturating_add(buffered)),
+            None => {
+                let mut hint = SizeHint::new();
+                hint.set_lower(inner.lower().saturating_add(buffered));
+                if let Some(upper) = inner.upper() {
+                    hint.set_upper(upper.saturating_add(buffered));
+                }
+                hint
+            },
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+
+///HTTP body, that emits the same `pattern` as separate frame specified number of times.
+///
+///Every frame is clone of the same reference counted buffer, so large body can be produced without allocating it.
+///Useful for load testing and fixtures.
+pub struct RepeatBody {
+    pattern: bytes::Bytes,
+    remaining: u64,
+}
+
+impl RepeatBody {
+    #[inline(always)]
+    ///Creates new instance, emitting `pattern` `count` times
+    pub const fn new(pattern: bytes::Bytes, count: u64) -> Self {
+        Self {
+            pattern,
+            remaining: count,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of frames remaining
+    pub const fn remaining(&self) -> u64 {
+        match self.pattern.is_empty() {
+            true => 0,
+            false => self.remaining,
+        }
+    }
+}
+
+impl HttpBody for RepeatBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn poll_frame(mut self: Pin<&mut Self>, _cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match self.remaining() {
+            0 => task::Poll::Ready(None),
+            remaining => {
+                self.remaining = remaining - 1;
+                task::Poll::Ready(Some(Ok(Frame::data(self.pattern.clone()))))
+            },
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact((self.pattern.len() as u64).saturating_mul(self.remaining()))
+    }
+}
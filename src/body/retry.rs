@@ -0,0 +1,85 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that re-creates underlying body on transient error.
+    ///
+    ///Retry is performed only if no frame has been emitted yet, as otherwise consumer would receive duplicated data.
+    ///Once first frame is emitted, errors are passed through as it is.
+    ///
+    ///## Arguments
+    ///
+    ///- `F` - Factory to create new instance of body.
+    ///- `P` - Predicate to determine whether error is transient.
+    #[project = RetryBodyProj]
+    pub struct RetryBody<T, F, P> {
+        #[pin]
+        body: T,
+        factory: F,
+        is_transient: P,
+        is_emitted: bool,
+        retries: usize,
+        max_retries: usize,
+    }
+}
+
+impl<T, F: FnMut() -> T, P> RetryBody<T, F, P> {
+    #[inline]
+    ///Creates new instance, allowing at most `max_retries` attempts to re-create body.
+    pub fn new(mut factory: F, is_transient: P, max_retries: usize) -> Self {
+        Self {
+            body: factory(),
+            factory,
+            is_transient,
+            is_emitted: false,
+            retries: 0,
+            max_retries,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of retries performed
+    pub const fn retries(&self) -> usize {
+        self.retries
+    }
+}
+
+impl<T: HttpBody, F: FnMut() -> T, P: FnMut(&T::Error) -> bool> HttpBody for RetryBody<T, F, P> {
+    type Data = T::Data;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let RetryBodyProj { mut body, factory, is_transient, is_emitted, retries, max_retries } = self.project();
+
+        loop {
+            match HttpBody::poll_frame(body.as_mut(), cx) {
+                task::Poll::Ready(Some(Ok(frame))) => {
+                    *is_emitted = true;
+                    break task::Poll::Ready(Some(Ok(frame)));
+                },
+                task::Poll::Ready(Some(Err(error))) => {
+                    if *is_emitted || *retries >= *max_retries || !(*is_transient)(&error) {
+                        break task::Poll::Ready(Some(Err(error)));
+                    }
+
+                    *retries += 1;
+                    body.set((*factory)());
+                },
+                task::Poll::Ready(None) => break task::Poll::Ready(None),
+                task::Poll::Pending => break task::Poll::Pending,
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
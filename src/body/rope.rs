@@ -0,0 +1,150 @@
+extern crate alloc;
+
+use alloc::collections::VecDeque;
+use core::mem;
+
+use bytes::{Buf, BufMut};
+
+use super::Collector;
+
+///Non-contiguous buffer, consisting of list of segments.
+///
+///Implements `bytes::Buf`, allowing to consume data without ever making it contiguous.
+#[derive(Clone, Debug, Default)]
+pub struct Rope {
+    segments: VecDeque<bytes::Bytes>,
+    len: usize,
+}
+
+impl Rope {
+    #[inline(always)]
+    ///Creates empty instance
+    pub const fn new() -> Self {
+        Self {
+            segments: VecDeque::new(),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    ///Appends segment to the end, ignoring empty ones
+    pub fn push(&mut self, segment: bytes::Bytes) {
+        if !segment.is_empty() {
+            self.len = self.len.saturating_add(segment.len());
+            self.segments.push_back(segment);
+        }
+    }
+
+    #[inline(always)]
+    ///Returns total number of remaining bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    ///Returns whether there is no data left
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    ///Returns iterator over remaining segments
+    pub fn segments(&self) -> impl Iterator<Item = &bytes::Bytes> {
+        self.segments.iter()
+    }
+}
+
+impl Buf for Rope {
+    #[inline(always)]
+    fn remaining(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn chunk(&self) -> &[u8] {
+        match self.segments.front() {
+            Some(segment) => segment,
+            None => &[],
+        }
+    }
+
+    fn advance(&mut self, mut cnt: usize) {
+        assert!(cnt <= self.len, "cannot advance past remaining: {} <= {}", cnt, self.len);
+        self.len -= cnt;
+
+        while cnt > 0 {
+            let segment = match self.segments.front_mut() {
+                Some(segment) => segment,
+                None => unreach!(),
+            };
+            if cnt < segment.len() {
+                segment.advance(cnt);
+                break;
+            }
+
+            cnt -= segment.len();
+            self.segments.pop_front();
+        }
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> bytes::Bytes {
+        match self.segments.front_mut() {
+            //Fast path: zero-copy slice of single segment
+            Some(segment) if len <= segment.len() => {
+                let result = segment.split_to(len);
+                self.len -= len;
+                if segment.is_empty() {
+                    self.segments.pop_front();
+                }
+                result
+            },
+            _ => {
+                assert!(len <= self.len, "`len` greater than remaining");
+                let mut result = bytes::BytesMut::with_capacity(len);
+                result.put((&mut *self).take(len));
+                result.freeze()
+            }
+        }
+    }
+}
+
+///Collector, that stores frames as it is in `Rope`, avoiding copying and large contiguous allocation
+#[derive(Default)]
+pub struct RopeCollector {
+    rope: Rope,
+}
+
+impl RopeCollector {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            rope: Rope::new(),
+        }
+    }
+}
+
+impl Collector for RopeCollector {
+    type Output = Rope;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.rope.push(data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.rope.len()
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        Ok(mem::take(&mut self.rope))
+    }
+}
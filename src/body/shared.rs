@@ -0,0 +1,83 @@
+extern crate alloc;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use super::Collector;
+
+///Collector, appending into buffer shared via `Arc<Mutex<Vec<u8>>>`.
+///
+///Allows other tasks to observe data accumulated so far, while collection proceeds.
+///Lock is held only for the duration of single append, so observers should keep their critical section short too.
+///
+///On `consume`, returns clone of shared buffer, leaving data in it.
+///Note that existing content of buffer is included into `len`, hence it counts towards limit of `Collect`.
+pub struct SharedCollector {
+    buffer: Arc<Mutex<Vec<u8>>>,
+}
+
+impl SharedCollector {
+    #[inline(always)]
+    ///Creates new instance with empty buffer
+    pub fn new() -> Self {
+        Self::with_buffer(Default::default())
+    }
+
+    #[inline(always)]
+    ///Creates new instance, appending into existing `buffer`
+    pub const fn with_buffer(buffer: Arc<Mutex<Vec<u8>>>) -> Self {
+        Self {
+            buffer,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns shared buffer
+    pub fn buffer(&self) -> &Arc<Mutex<Vec<u8>>> {
+        &self.buffer
+    }
+
+    #[inline(always)]
+    fn lock(&self) -> MutexGuard<'_, Vec<u8>> {
+        //Appending bytes cannot leave buffer in inconsistent state
+        self.buffer.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Default for SharedCollector {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for SharedCollector {
+    type Output = Arc<Mutex<Vec<u8>>>;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.lock().extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.lock().reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        Ok(self.buffer.clone())
+    }
+}
@@ -0,0 +1,330 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, mem, str};
+
+use super::Collector;
+
+//Magic bytes, optionally at offset, and corresponding media type.
+//Short signatures are additionally validated by `check`, as they are easily matched by text.
+struct Magic {
+    offset: usize,
+    bytes: &'static [u8],
+    mime: &'static str,
+    check: fn(&[u8]) -> bool,
+}
+
+const fn magic(offset: usize, bytes: &'static [u8], mime: &'static str) -> Magic {
+    magic_checked(offset, bytes, mime, |_| true)
+}
+
+const fn magic_checked(offset: usize, bytes: &'static [u8], mime: &'static str, check: fn(&[u8]) -> bool) -> Magic {
+    Magic {
+        offset,
+        bytes,
+        mime,
+        check,
+    }
+}
+
+//Reserved fields are zero and DIB header has one of known sizes
+fn is_bmp(data: &[u8]) -> bool {
+    match data.get(6..18) {
+        Some(header) => header[..4] == [0; 4] && matches!(u32::from_le_bytes([header[8], header[9], header[10], header[11]]), 12 | 40 | 52 | 56 | 64 | 108 | 124),
+        None => false,
+    }
+}
+
+//Has at least one image, and first directory entry has zero reserved byte and valid number of color planes
+fn is_ico(data: &[u8]) -> bool {
+    match data.get(4..12) {
+        Some(header) => u16::from_le_bytes([header[0], header[1]]) > 0 && header[5] == 0 && matches!(u16::from_le_bytes([header[6], header[7]]), 0 | 1),
+        None => false,
+    }
+}
+
+//Known major version and revision, no undefined flags and synchsafe size
+fn is_id3(data: &[u8]) -> bool {
+    let undefined_flags = match data.get(3..5) {
+        Some([2, 0]) => 0x3f,
+        Some([3, 0]) => 0x1f,
+        Some([4, 0]) => 0x0f,
+        _ => return false,
+    };
+    match data.get(5..10) {
+        Some(header) => header[0] & undefined_flags == 0 && header[1..].iter().all(|byte| *byte < 0x80),
+        None => false,
+    }
+}
+
+//Order matters for types sharing prefix (e.g. `RIFF` container)
+const MAGIC: &[Magic] = &[
+    magic(0, b"\x89PNG\r\n\x1a\n", "image/png"),
+    magic(0, b"\xff\xd8\xff", "image/jpeg"),
+    magic(0, b"GIF87a", "image/gif"),
+    magic(0, b"GIF89a", "image/gif"),
+    magic(8, b"WEBP", "image/webp"),
+    magic_checked(0, b"BM", "image/bmp", is_bmp),
+    magic_checked(0, b"\x00\x00\x01\x00", "image/x-icon", is_ico),
+    magic(4, b"ftypavif", "image/avif"),
+    magic(4, b"ftypheic", "image/heic"),
+    magic(4, b"ftyp", "video/mp4"),
+    magic(0, b"\x1a\x45\xdf\xa3", "video/webm"),
+    magic(0, b"OggS", "audio/ogg"),
+    magic(0, b"fLaC", "audio/flac"),
+    magic_checked(0, b"ID3", "audio/mpeg", is_id3),
+    magic(8, b"WAVE", "audio/wav"),
+    magic(0, b"%PDF-", "application/pdf"),
+    magic(0, b"PK\x03\x04", "application/zip"),
+    magic(0, b"PK\x05\x06", "application/zip"),
+    magic(0, b"\x1f\x8b", "application/gzip"),
+    magic(0, b"\x28\xb5\x2f\xfd", "application/zstd"),
+    magic(0, b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    magic(0, b"Rar!\x1a\x07", "application/vnd.rar"),
+    magic(0, b"\x00asm", "application/wasm"),
+    magic(0, b"wOFF", "font/woff"),
+    magic(0, b"wOF2", "font/woff2"),
+];
+
+///Number of leading bytes, sufficient for `sniff`
+pub const SNIFF_LEN: usize = 18;
+
+///Detects media type of `data` by its magic bytes, returning `None` if it is not recognized.
+///
+///Only binary formats with reliable signature are recognized (images, audio/video, archives, `PDF`, fonts, `WASM`).
+///`RIFF` based types (`WEBP`, `WAV`) additionally require `RIFF` prefix,
+///while short signatures (`BMP`, `ICO`, `ID3`) require valid header fields after them.
+pub fn sniff(data: &[u8]) -> Option<&'static str> {
+    for magic in MAGIC {
+        let end = magic.offset + magic.bytes.len();
+        if data.len() < end || &data[magic.offset..end] != magic.bytes {
+            continue;
+        }
+        //Share `RIFF` container prefix
+        if magic.offset == 8 && !data.starts_with(b"RIFF") {
+            continue;
+        }
+        if !(magic.check)(data) {
+            continue;
+        }
+        return Some(magic.mime);
+    }
+
+    None
+}
+
+#[derive(Debug)]
+///`SniffCollector` error
+pub enum SniffError {
+    ///Content type cannot be detected
+    Unknown,
+    ///Detected content type differs from declared one
+    Mismatch {
+        ///Declared media type
+        expected: String,
+        ///Detected media type
+        actual: &'static str,
+    },
+}
+
+impl fmt::Display for SniffError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Unknown => fmt.write_str("UnknownContentType"),
+            Self::Mismatch { expected, actual } => fmt.write_fmt(format_args!("ContentTypeMismatch(expected {}, detected {})", expected, actual)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SniffError {}
+
+///Collector, that verifies content matches declared media type by sniffing its magic bytes.
+///
+///Check is performed as soon as `SNIFF_LEN` bytes are collected, or on `consume` for shorter bodies.
+///Media type parameters are ignored and comparison is case insensitive.
+pub struct SniffCollector {
+    expected: String,
+    is_checked: bool,
+    buffer: Vec<u8>,
+}
+
+impl SniffCollector {
+    #[inline]
+    ///Creates new instance, expecting `mime` media type (e.g. `image/png`)
+    pub fn new(mime: &str) -> Self {
+        let mime = match mime.split_once(';') {
+            Some((mime, _)) => mime,
+            None => mime,
+        };
+        Self {
+            expected: mime.trim().into(),
+            is_checked: false,
+            buffer: Vec::new(),
+        }
+    }
+
+    #[inline]
+    ///Creates new instance, expecting media type of `Content-Type` header value.
+    ///
+    ///Non-ASCII value is never matched.
+    pub fn from_content_type(value: &http::HeaderValue) -> Self {
+        Self::new(value.to_str().unwrap_or_default())
+    }
+
+    fn check(&mut self) -> Option<SniffError> {
+        self.is_checked = true;
+        match sniff(&self.buffer) {
+            Some(actual) if actual.eq_ignore_ascii_case(&self.expected) => None,
+            Some(actual) => Some(SniffError::Mismatch {
+                expected: self.expected.clone(),
+                actual,
+            }),
+            None => Some(SniffError::Unknown),
+        }
+    }
+}
+
+impl Collector for SniffCollector {
+    type Output = Vec<u8>;
+    type Error = SniffError;
+
+    #[inline]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.buffer.extend_from_slice(&data);
+        match !self.is_checked && self.buffer.len() >= SNIFF_LEN {
+            true => self.check(),
+            false => None,
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        let error = match self.is_checked {
+            true => None,
+            false => self.check(),
+        };
+
+        //Next body must be sniffed again
+        self.is_checked = false;
+        let buffer = mem::take(&mut self.buffer);
+        match error {
+            Some(error) => Err(error),
+            None => Ok(buffer),
+        }
+    }
+}
+
+//Number of leading bytes, inspected to detect text content
+const TEXT_SNIFF_LEN: usize = 512;
+
+//Detects whether `data` is `UTF-8` text, and if it looks like JSON.
+//`is_prefix` indicates `data` may be cut in the middle of code point.
+fn sniff_text(data: &[u8], is_prefix: bool) -> Option<&'static str> {
+    let is_utf8 = match str::from_utf8(data) {
+        Ok(_) => true,
+        Err(error) => is_prefix && error.error_len().is_none(),
+    };
+    if data.is_empty() || !is_utf8 || data.iter().any(|byte| byte.is_ascii_control() && !matches!(byte, b'\t' | b'\n' | b'\r' | b'\x0c')) {
+        return None;
+    }
+
+    match data.iter().find(|byte| !byte.is_ascii_whitespace()) {
+        Some(b'{' | b'[') => Some("application/json"),
+        _ => Some("text/plain"),
+    }
+}
+
+///Collector wrapper, that detects media type of body by its leading bytes, passing all data to underlying collector `C`.
+///
+///Binary formats are detected using `sniff` as soon as `SNIFF_LEN` bytes are received.
+///Otherwise first 512 bytes are inspected for `UTF-8` text, detected as `text/plain`, or `application/json` if it starts with object or array.
+///Once detected, wrapper becomes transparent.
+///
+///Output is tuple of underlying collector output and detected media type, if any.
+///Useful to set `Content-Type` for body, that lacks reliable one.
+pub struct MimeSniffCollector<C> {
+    inner: C,
+    prefix: Vec<u8>,
+    mime: Option<&'static str>,
+    is_detected: bool,
+}
+
+impl<C: Collector> MimeSniffCollector<C> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(inner: C) -> Self {
+        Self {
+            inner,
+            prefix: Vec::new(),
+            mime: None,
+            is_detected: false,
+        }
+    }
+
+    fn detect(&mut self, is_complete: bool) {
+        if self.prefix.len() >= SNIFF_LEN || is_complete {
+            self.mime = sniff(&self.prefix);
+        }
+        if self.mime.is_none() && (self.prefix.len() >= TEXT_SNIFF_LEN || is_complete) {
+            self.mime = sniff_text(&self.prefix, !is_complete);
+        }
+        if self.mime.is_some() || self.prefix.len() >= TEXT_SNIFF_LEN || is_complete {
+            self.is_detected = true;
+            self.prefix = Vec::new();
+        }
+    }
+}
+
+impl<C: Collector> Collector for MimeSniffCollector<C> {
+    type Output = (C::Output, Option<&'static str>);
+    type Error = C::Error;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        if !self.is_detected {
+            let len = core::cmp::min(data.len(), TEXT_SNIFF_LEN - self.prefix.len());
+            self.prefix.extend_from_slice(&data[..len]);
+            self.detect(false);
+        }
+        self.inner.append(data)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, headers: http::HeaderMap) {
+        self.inner.on_trailers(headers)
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        if !self.is_detected {
+            self.detect(true);
+        }
+        let output = self.inner.consume()?;
+        self.is_detected = false;
+        Ok((output, self.mime.take()))
+    }
+}
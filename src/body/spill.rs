@@ -0,0 +1,168 @@
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::mem;
+use core::hash::BuildHasher;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::collections::hash_map::RandomState;
+use std::io::{self, Seek, Write};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::Collector;
+
+static SPILL_COUNTER: AtomicUsize = AtomicUsize::new(0);
+//Number of attempts to pick unique file name
+const SPILL_ATTEMPTS: usize = 16;
+
+///Temporary file, containing body spilled to disk.
+///
+///File name contains random component, and on Unix file is accessible only by its owner.
+///File is removed on drop.
+#[derive(Debug)]
+pub struct SpilledFile {
+    file: fs::File,
+    path: PathBuf,
+}
+
+impl SpilledFile {
+    fn create(dir: &Path) -> io::Result<Self> {
+        let mut options = fs::OpenOptions::new();
+        options.read(true).write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut attempt = 0;
+        loop {
+            //RandomState is seeded from OS randomness, hence name cannot be guessed in advance
+            let random = RandomState::new().hash_one(SPILL_COUNTER.fetch_add(1, Ordering::Relaxed));
+            let path = dir.join(alloc::format!("http-fancy-spill-{}-{:016x}", std::process::id(), random));
+            match options.open(&path) {
+                Ok(file) => break Ok(Self {
+                    file,
+                    path,
+                }),
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists && attempt < SPILL_ATTEMPTS => attempt += 1,
+                Err(error) => break Err(error),
+            }
+        }
+    }
+
+    #[inline(always)]
+    ///Access file, positioned at the start of collected data once returned by collector
+    pub fn file(&mut self) -> &mut fs::File {
+        &mut self.file
+    }
+
+    #[inline(always)]
+    ///Returns path to the file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for SpilledFile {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+///Output of `SpillCollector`
+#[derive(Debug)]
+pub enum Spilled {
+    ///Body fits within threshold and stays in memory
+    Memory(bytes::Bytes),
+    ///Body exceeds threshold, and is written to temporary file
+    File(SpilledFile),
+}
+
+enum SpillState {
+    Memory(Vec<u8>),
+    File(SpilledFile),
+}
+
+///Collector, that buffers in memory up to threshold, after which all data is written into temporary file.
+///
+///This bounds memory usage, while still allowing to accept large bodies.
+pub struct SpillCollector {
+    threshold: usize,
+    dir: PathBuf,
+    len: usize,
+    state: SpillState,
+}
+
+impl SpillCollector {
+    #[inline]
+    ///Creates new instance, spilling to system's temporary directory after `threshold` bytes
+    pub fn new(threshold: usize) -> Self {
+        Self::with_dir(threshold, std::env::temp_dir())
+    }
+
+    #[inline(always)]
+    ///Creates new instance, spilling to `dir` after `threshold` bytes
+    pub fn with_dir(threshold: usize, dir: PathBuf) -> Self {
+        Self {
+            threshold,
+            dir,
+            len: 0,
+            state: SpillState::Memory(Vec::new()),
+        }
+    }
+}
+
+impl Collector for SpillCollector {
+    type Output = Spilled;
+    type Error = io::Error;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        let result = match &mut self.state {
+            SpillState::Memory(buffer) => match buffer.len().saturating_add(data.len()) > self.threshold {
+                false => {
+                    buffer.extend_from_slice(&data);
+                    Ok(())
+                },
+                true => match SpilledFile::create(&self.dir) {
+                    Ok(mut spilled) => match spilled.file.write_all(buffer).and_then(|_| spilled.file.write_all(&data)) {
+                        Ok(()) => {
+                            self.state = SpillState::File(spilled);
+                            Ok(())
+                        },
+                        Err(error) => Err(error),
+                    },
+                    Err(error) => Err(error),
+                },
+            },
+            SpillState::File(spilled) => spilled.file.write_all(&data),
+        };
+
+        match result {
+            Ok(()) => {
+                self.len = self.len.saturating_add(data.len());
+                None
+            },
+            Err(error) => Some(error),
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        self.len = 0;
+        match mem::replace(&mut self.state, SpillState::Memory(Vec::new())) {
+            SpillState::Memory(buffer) => Ok(Spilled::Memory(buffer.into())),
+            SpillState::File(mut spilled) => {
+                spilled.file.flush()?;
+                spilled.file.rewind()?;
+                Ok(Spilled::File(spilled))
+            }
+        }
+    }
+}
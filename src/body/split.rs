@@ -0,0 +1,96 @@
+use core::{fmt, mem};
+
+use super::{Collect, Collector};
+
+#[derive(Debug, PartialEq, Eq)]
+///`SplitCollector` error
+pub enum SplitError {
+    ///Body ended before header is complete
+    TooShort {
+        ///Number of bytes received
+        actual: usize,
+    },
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TooShort { actual } => fmt.write_fmt(format_args!("TooShort({} bytes)", actual)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SplitError {}
+
+///Collector, that splits body into fixed size header of `H` bytes and the rest.
+///
+///Output is `(header, payload)`.
+pub struct SplitCollector<const H: usize> {
+    head: bytes::BytesMut,
+    tail: bytes::BytesMut,
+}
+
+impl<const H: usize> SplitCollector<H> {
+    #[inline(always)]
+    ///Creates new instance
+    pub fn new() -> Self {
+        Self {
+            head: bytes::BytesMut::with_capacity(H),
+            tail: bytes::BytesMut::new(),
+        }
+    }
+}
+
+impl<const H: usize> Default for SplitCollector<H> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const H: usize> Collector for SplitCollector<H> {
+    type Output = (bytes::Bytes, bytes::Bytes);
+    type Error = SplitError;
+
+    fn append(&mut self, mut data: bytes::Bytes) -> Option<Self::Error> {
+        let need = H - self.head.len();
+        if need > 0 {
+            let head = data.split_to(core::cmp::min(need, data.len()));
+            self.head.extend_from_slice(&head);
+        }
+        self.tail.extend_from_slice(&data);
+        None
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.head.len() + self.tail.len()
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        let head = mem::take(&mut self.head).freeze();
+        let tail = mem::take(&mut self.tail).freeze();
+        match head.len() == H {
+            true => Ok((head, tail)),
+            false => Err(SplitError::TooShort {
+                actual: head.len() + tail.len(),
+            })
+        }
+    }
+}
+
+///Future that collects `HttpBody`, splitting it into header of `H` bytes and the rest, with total size limit `S`
+pub type SplitCollect<const H: usize, const S: usize, T> = Collect<S, T, SplitCollector<H>>;
+
+impl<T, const H: usize, const S: usize> Collect<S, T, SplitCollector<H>> {
+    #[inline(always)]
+    ///Creates new instance, splitting body into header and payload
+    pub fn split(body: T) -> Self {
+        Self::new(body, SplitCollector::new())
+    }
+}
@@ -0,0 +1,132 @@
+extern crate alloc;
+
+use alloc::string::String;
+use core::pin::Pin;
+use core::{task, fmt};
+
+use super::{HttpBody, Frame};
+use super::channel::{self, Sender, Receiver};
+
+///Possible errors when sending event via `SseSender`
+#[derive(Debug, PartialEq, Eq)]
+pub enum SseError {
+    ///`SseBody` is dropped
+    Closed,
+    ///Event name contains line break
+    InvalidEventName,
+}
+
+impl fmt::Display for SseError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Closed => fmt.write_str("Closed"),
+            Self::InvalidEventName => fmt.write_str("InvalidEventName"),
+        }
+    }
+}
+
+impl std::error::Error for SseError {}
+
+#[derive(Clone)]
+///Sender of Server-Sent Events into paired `SseBody`
+///
+///Once all senders are dropped, body ends.
+pub struct SseSender {
+    sender: Sender<bytes::Bytes>,
+}
+
+impl SseSender {
+    #[inline]
+    ///Sends event with `data` only, which is dispatched as `message` by client
+    pub fn send_data(&self, data: &str) -> Result<(), SseError> {
+        self.send(None, data)
+    }
+
+    #[inline]
+    ///Sends named `event` with `data`
+    ///
+    ///Each line of `data` is sent as separate `data:` field, where lines are terminated by `\r\n`, `\r` or `\n` as per SSE specification.
+    pub fn send_event(&self, event: &str, data: &str) -> Result<(), SseError> {
+        self.send(Some(event), data)
+    }
+
+    #[inline(always)]
+    ///Returns whether `SseBody` is dropped
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+
+    fn send(&self, event: Option<&str>, data: &str) -> Result<(), SseError> {
+        let mut frame = String::with_capacity(data.len() + 16);
+        if let Some(event) = event {
+            if event.contains(['\r', '\n']) {
+                return Err(SseError::InvalidEventName);
+            }
+            frame.push_str("event: ");
+            frame.push_str(event);
+            frame.push('\n');
+        }
+        //Client treats lone `\r` as line break too, so it must not leak into field
+        let mut data = data;
+        loop {
+            frame.push_str("data: ");
+            match data.find(['\r', '\n']) {
+                Some(idx) => {
+                    frame.push_str(&data[..idx]);
+                    frame.push('\n');
+                    data = match data[idx..].starts_with("\r\n") {
+                        true => &data[idx + 2..],
+                        false => &data[idx + 1..],
+                    };
+                },
+                None => {
+                    frame.push_str(data);
+                    frame.push('\n');
+                    break;
+                }
+            }
+        }
+        frame.push('\n');
+
+        self.sender.send(frame.into()).map_err(|_| SseError::Closed)
+    }
+}
+
+///Server-Sent Events body, emitting each event sent via paired `SseSender` as separate frame.
+///
+///Size of body is unknown, and body ends once all senders are dropped.
+pub struct SseBody {
+    receiver: Receiver<bytes::Bytes>,
+}
+
+impl SseBody {
+    #[inline]
+    ///Creates new body with paired sender
+    pub fn channel() -> (SseSender, Self) {
+        let (sender, receiver) = channel::channel();
+        (SseSender { sender }, Self { receiver })
+    }
+}
+
+impl HttpBody for SseBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn poll_frame(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        self.receiver.poll_recv(cx).map(|event| event.map(|event| Ok(Frame::data(event))))
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.receiver.is_terminated()
+    }
+}
+
+impl fmt::Debug for SseBody {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("SseBody")
+    }
+}
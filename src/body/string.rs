@@ -0,0 +1,117 @@
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::{fmt, mem, str};
+
+use super::Collector;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///`StringCollector` error
+pub enum StringError {
+    ///Body contains invalid `UTF-8` sequence
+    InvalidUtf8 {
+        ///Length of valid prefix of body
+        valid_up_to: usize,
+    },
+    ///Body ends in the middle of `UTF-8` sequence
+    Incomplete {
+        ///Length of valid prefix of body
+        valid_up_to: usize,
+    },
+}
+
+impl fmt::Display for StringError {
+    #[inline(always)]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8 { valid_up_to } => fmt.write_fmt(format_args!("InvalidUtf8(valid up to {})", valid_up_to)),
+            Self::Incomplete { valid_up_to } => fmt.write_fmt(format_args!("IncompleteUtf8(valid up to {})", valid_up_to)),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StringError {}
+
+///Collector, that collects body into `String`, validating `UTF-8` as data arrives.
+///
+///Only newly appended data is validated on every append, while code point split across frames is completed by the next one.
+///Hence invalid body is rejected early, and no validation pass is needed at the end.
+pub struct StringCollector {
+    buffer: Vec<u8>,
+    //Length of validated prefix, the rest is incomplete code point of at most 3 bytes
+    valid: usize,
+}
+
+impl StringCollector {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            valid: 0,
+        }
+    }
+}
+
+impl Default for StringCollector {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Collector for StringCollector {
+    type Output = String;
+    type Error = StringError;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.buffer.extend_from_slice(&data);
+        match str::from_utf8(&self.buffer[self.valid..]) {
+            Ok(_) => {
+                self.valid = self.buffer.len();
+                None
+            },
+            Err(error) => match error.error_len() {
+                //Incomplete sequence at the end, to be completed by next append
+                None => {
+                    self.valid += error.valid_up_to();
+                    None
+                },
+                Some(_) => Some(StringError::InvalidUtf8 {
+                    valid_up_to: self.valid + error.valid_up_to(),
+                }),
+            },
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.buffer.reserve(additional)
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        if self.valid != self.buffer.len() {
+            return Err(StringError::Incomplete {
+                valid_up_to: self.valid,
+            });
+        }
+
+        self.valid = 0;
+        let buffer = mem::take(&mut self.buffer);
+        //Whole buffer is validated by append
+        Ok(unsafe {
+            String::from_utf8_unchecked(buffer)
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use core::pin::Pin;
+use core::{cmp, task};
+
+use super::{HttpBody, Frame, SizeHint};
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that emits at most `limit` bytes of underlying body, similarly to `std::io::Read::take`.
+    ///
+    ///Frame crossing limit is truncated, after which body ends without polling underlying body further.
+    ///Trailers are passed only if underlying body ends before limit is reached.
+    #[project = TakeBodyProj]
+    pub struct TakeBody<T> {
+        #[pin]
+        body: T,
+        remaining: u64,
+    }
+}
+
+impl<T> TakeBody<T> {
+    #[inline(always)]
+    ///Creates new instance, emitting at most `limit` bytes of `body`
+    pub const fn new(body: T, limit: u64) -> Self {
+        Self {
+            body,
+            remaining: limit,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes that can still be emitted
+    pub const fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    #[inline(always)]
+    ///Returns underlying body
+    pub fn into_inner(self) -> T {
+        self.body
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>> HttpBody for TakeBody<T> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let TakeBodyProj { body, remaining } = self.project();
+        if *remaining == 0 {
+            return task::Poll::Ready(None);
+        }
+
+        match HttpBody::poll_frame(body, cx) {
+            task::Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                Ok(mut data) => {
+                    if data.len() as u64 > *remaining {
+                        data.truncate(*remaining as usize);
+                    }
+                    *remaining -= data.len() as u64;
+                    task::Poll::Ready(Some(Ok(Frame::data(data))))
+                },
+                Err(frame) => task::Poll::Ready(Some(Ok(frame))),
+            },
+            result => result,
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.remaining == 0 || self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let hint = self.body.size_hint();
+        let upper = cmp::min(hint.upper().unwrap_or(self.remaining), self.remaining);
+
+        let mut result = SizeHint::new();
+        result.set_upper(upper);
+        result.set_lower(cmp::min(hint.lower(), upper));
+        result
+    }
+}
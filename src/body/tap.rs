@@ -0,0 +1,60 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, Frame, SizeHint};
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that invokes callback on every data frame passing through, without altering it.
+    ///
+    ///Useful to log or audit outgoing bodies.
+    #[project = TapBodyProj]
+    pub struct TapBody<T, F> {
+        #[pin]
+        body: T,
+        tap: F,
+    }
+}
+
+impl<T, F: FnMut(&bytes::Bytes)> TapBody<T, F> {
+    #[inline(always)]
+    ///Creates new instance, invoking `tap` on every data frame of `body`
+    pub const fn new(body: T, tap: F) -> Self {
+        Self {
+            body,
+            tap,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns underlying body and callback
+    pub fn into_parts(self) -> (T, F) {
+        (self.body, self.tap)
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>, F: FnMut(&bytes::Bytes)> HttpBody for TapBody<T, F> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let TapBodyProj { body, tap } = self.project();
+
+        let result = HttpBody::poll_frame(body, cx);
+        if let task::Poll::Ready(Some(Ok(frame))) = &result {
+            if let Some(data) = frame.data_ref() {
+                (*tap)(data);
+            }
+        }
+        result
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
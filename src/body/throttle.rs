@@ -0,0 +1,191 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::future::Future;
+use core::pin::Pin;
+use core::{cmp, task};
+use core::time::Duration;
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+use super::{HttpBody, Frame, SizeHint};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+struct Bucket {
+    tokens: u64,
+    last: Instant,
+}
+
+///Token bucket rate limiter, to be shared across multiple `ThrottledBody` via `Arc`.
+///
+///Caps total throughput of all bodies at `rate` bytes per second, allowing bursts up to `burst` bytes.
+///To keep bodies from starving each other, single grant never exceeds `quantum` bytes, so competing bodies get their turn after each chunk.
+pub struct SharedRateLimiter {
+    rate: u64,
+    burst: u64,
+    quantum: u64,
+    clock: fn() -> Instant,
+    bucket: Mutex<Bucket>,
+}
+
+impl SharedRateLimiter {
+    ///Creates new limiter with `rate` bytes per second and `burst` bytes capacity.
+    ///
+    ///Bucket starts full, and `quantum` defaults to `burst`.
+    ///
+    ///Panics if `rate` or `burst` is zero.
+    pub fn new(rate: u64, burst: u64) -> Self {
+        assert!(rate > 0, "rate cannot be zero");
+        assert!(burst > 0, "burst cannot be zero");
+        Self {
+            rate,
+            burst,
+            quantum: burst,
+            clock: Instant::now,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                last: Instant::now(),
+            }),
+        }
+    }
+
+    #[inline(always)]
+    ///Sets maximum number of bytes granted at once, clamped to `1..=burst`.
+    pub fn with_quantum(mut self, quantum: u64) -> Self {
+        self.quantum = quantum.clamp(1, self.burst);
+        self
+    }
+
+    #[inline]
+    ///Sets source of current time, used instead of `Instant::now` (e.g. to use runtime's clock, that can be paused in tests).
+    ///
+    ///Bucket is considered to be full at the time returned by `clock`.
+    pub fn with_clock(mut self, clock: fn() -> Instant) -> Self {
+        self.clock = clock;
+        self.bucket.get_mut().unwrap_or_else(PoisonError::into_inner).last = clock();
+        self
+    }
+
+    ///Attempts to acquire up to `wanted` bytes, returning number of granted bytes, or time to wait until next byte is available.
+    pub fn acquire(&self, wanted: usize) -> Result<usize, Duration> {
+        let now = (self.clock)();
+        //Bucket state is always consistent
+        let mut bucket = self.bucket.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let elapsed = now.saturating_duration_since(bucket.last).as_nanos();
+        let refill = elapsed.saturating_mul(self.rate as u128) / NANOS_PER_SEC;
+        if refill > 0 {
+            let tokens = (bucket.tokens as u128).saturating_add(refill);
+            match tokens >= self.burst as u128 {
+                true => {
+                    bucket.tokens = self.burst;
+                    bucket.last = now;
+                },
+                false => {
+                    bucket.tokens = tokens as u64;
+                    //Carry over fraction of token
+                    bucket.last += Duration::from_nanos((refill * NANOS_PER_SEC / self.rate as u128) as u64);
+                },
+            }
+        }
+
+        match bucket.tokens {
+            0 => {
+                let per_token = NANOS_PER_SEC.div_ceil(self.rate as u128);
+                let elapsed = now.saturating_duration_since(bucket.last).as_nanos();
+                Err(Duration::from_nanos(per_token.saturating_sub(elapsed).max(1) as u64))
+            },
+            tokens => {
+                let granted = cmp::min(cmp::min(tokens, self.quantum), wanted as u64);
+                bucket.tokens -= granted;
+                Ok(granted as usize)
+            }
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    ///HTTP body wrapper, that limits output rate using `SharedRateLimiter`.
+    ///
+    ///Data frames are split into chunks according to granted bytes.
+    ///When limiter is exhausted, body waits using sleep future created by `F`, hence it is not tied to any particular runtime
+    ///(e.g. `tokio::time::sleep` can be used).
+    #[project = ThrottledBodyProj]
+    pub struct ThrottledBody<T, F, S> {
+        #[pin]
+        body: T,
+        limiter: Arc<SharedRateLimiter>,
+        new_sleep: F,
+        sleep: Option<Pin<Box<S>>>,
+        pending: bytes::Bytes,
+    }
+}
+
+impl<T, F: FnMut(Duration) -> S, S: Future<Output = ()>> ThrottledBody<T, F, S> {
+    #[inline(always)]
+    ///Creates new instance, using `new_sleep` to create timer for specified duration
+    pub const fn new(body: T, limiter: Arc<SharedRateLimiter>, new_sleep: F) -> Self {
+        Self {
+            body,
+            limiter,
+            new_sleep,
+            sleep: None,
+            pending: bytes::Bytes::new(),
+        }
+    }
+}
+
+impl<T: HttpBody<Data = bytes::Bytes>, F: FnMut(Duration) -> S, S: Future<Output = ()>> HttpBody for ThrottledBody<T, F, S> {
+    type Data = bytes::Bytes;
+    type Error = T::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let ThrottledBodyProj { mut body, limiter, new_sleep, sleep, pending } = self.project();
+
+        loop {
+            if let Some(timer) = sleep.as_mut() {
+                match Future::poll(timer.as_mut(), cx) {
+                    task::Poll::Ready(()) => *sleep = None,
+                    task::Poll::Pending => break task::Poll::Pending,
+                }
+            }
+
+            if pending.is_empty() {
+                match HttpBody::poll_frame(body.as_mut(), cx) {
+                    task::Poll::Ready(Some(Ok(frame))) => match frame.into_data() {
+                        Ok(data) => match data.is_empty() {
+                            true => continue,
+                            false => *pending = data,
+                        },
+                        Err(frame) => break task::Poll::Ready(Some(Ok(frame))),
+                    },
+                    result => break result,
+                }
+            }
+
+            match limiter.acquire(pending.len()) {
+                Ok(granted) => break task::Poll::Ready(Some(Ok(Frame::data(pending.split_to(granted))))),
+                Err(wait) => *sleep = Some(Box::pin((*new_sleep)(wait))),
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_empty() && self.body.is_end_stream()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> SizeHint {
+        let hint = self.body.size_hint();
+        let pending = self.pending.len() as u64;
+        let mut result = SizeHint::new();
+        result.set_lower(hint.lower().saturating_add(pending));
+        if let Some(upper) = hint.upper() {
+            result.set_upper(upper.saturating_add(pending));
+        }
+        result
+    }
+}
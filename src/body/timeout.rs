@@ -0,0 +1,67 @@
+extern crate alloc;
+
+use alloc::boxed::Box;
+use core::future::Future;
+use core::pin::Pin;
+use core::task;
+use core::time::Duration;
+
+use super::{HttpBody, Collect, Collector, CollectError};
+
+///`Collect` wrapper, that requires body to be collected completely within `timeout`.
+///
+///Timer starts on the first poll, and if it fires before body is collected, `CollectError::Timeout` is returned.
+///Unlike `CollectMinRate`, this detects peers that stall forever without waking.
+///
+///Timer is created using `F`, hence it is not tied to any particular runtime (e.g. `tokio::time::sleep` can be used).
+pub struct CollectTimeout<const S: usize, T, C, F, Z> {
+    collect: Collect<S, T, C>,
+    timeout: Duration,
+    new_sleep: F,
+    sleep: Option<Pin<Box<Z>>>,
+}
+
+pin_invariants!(impl[const S: usize, T, C, F, Z] CollectTimeout<S, T, C, F, Z> where Collect<S, T, C>: Unpin);
+
+impl<T, C, const S: usize, F: FnMut(Duration) -> Z, Z: Future<Output = ()>> CollectTimeout<S, T, C, F, Z> {
+    #[inline(always)]
+    ///Creates new instance, failing if `collect` is not complete within `timeout`
+    pub const fn new(collect: Collect<S, T, C>, timeout: Duration, new_sleep: F) -> Self {
+        Self {
+            collect,
+            timeout,
+            new_sleep,
+            sleep: None,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns underlying future
+    pub fn into_inner(self) -> Collect<S, T, C> {
+        self.collect
+    }
+}
+
+impl<E, D: bytes::Buf, T: HttpBody<Data = D, Error = E>, C: Collector, const S: usize, F: FnMut(Duration) -> Z, Z: Future<Output = ()>> Future for CollectTimeout<S, T, C, F, Z> {
+    type Output = Result<C::Output, CollectError<E, C::Error, C::Output>>;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
+        project!(self => CollectTimeout { #[pin] collect, timeout, new_sleep, sleep });
+
+        match Future::poll(collect.as_mut(), ctx) {
+            task::Poll::Ready(result) => task::Poll::Ready(result),
+            task::Poll::Pending => {
+                let timer = match sleep.as_mut() {
+                    Some(timer) => timer,
+                    None => sleep.insert(Box::pin((*new_sleep)(*timeout))),
+                };
+                match Future::poll(timer.as_mut(), ctx) {
+                    task::Poll::Ready(()) => task::Poll::Ready(Err(CollectError::Timeout {
+                        received: collect.received(),
+                    })),
+                    task::Poll::Pending => task::Poll::Pending,
+                }
+            }
+        }
+    }
+}
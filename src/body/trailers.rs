@@ -0,0 +1,70 @@
+use core::mem;
+
+use super::Collector;
+
+///Collector wrapper, that captures trailers, while delegating data to inner collector.
+///
+///Output is `(inner output, trailers)`, where trailers are `None` if body has none.
+///If there are multiple trailer frames, they are merged into single map, keeping all values.
+///Inner collector receives its own copy of every trailer frame.
+pub struct TrailersCollector<C> {
+    inner: C,
+    trailers: Option<http::HeaderMap>,
+}
+
+impl<C> TrailersCollector<C> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(inner: C) -> Self {
+        Self {
+            inner,
+            trailers: None,
+        }
+    }
+}
+
+impl<C: Collector> Collector for TrailersCollector<C> {
+    type Output = (C::Output, Option<http::HeaderMap>);
+    type Error = C::Error;
+
+    #[inline(always)]
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        self.inner.append(data)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional)
+    }
+
+    fn on_trailers(&mut self, headers: http::HeaderMap) {
+        self.inner.on_trailers(headers.clone());
+
+        match self.trailers.as_mut() {
+            Some(trailers) => {
+                //Subsequent values of the same header come without name
+                let mut last_name = None;
+                for (name, value) in headers {
+                    if name.is_some() {
+                        last_name = name;
+                    }
+                    if let Some(name) = last_name.as_ref() {
+                        trailers.append(name, value);
+                    }
+                }
+            },
+            None => self.trailers = Some(headers),
+        }
+    }
+
+    #[inline(always)]
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        let output = self.inner.consume()?;
+        Ok((output, mem::take(&mut self.trailers)))
+    }
+}
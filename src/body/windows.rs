@@ -0,0 +1,88 @@
+use core::pin::Pin;
+use core::task;
+
+use super::{HttpBody, CollectError};
+
+///Stream of fixed `W` bytes windows over `HttpBody`, with total size limit `S`.
+///
+///Windows are assembled across frame boundaries, and the last window contains remainder of body, if any.
+///Trailers are ignored.
+///
+///Implements `futures_core::Stream` with `stream` feature, otherwise provides `poll_next` with the same contract.
+pub struct CollectWindows<const W: usize, const S: usize, T> {
+    body: T,
+    buffer: bytes::BytesMut,
+    received: u64,
+    is_finished: bool,
+}
+
+pin_invariants!(impl[const W: usize, const S: usize, T] CollectWindows<W, S, T> where T: Unpin);
+
+impl<const W: usize, const S: usize, T> CollectWindows<W, S, T> {
+    #[inline(always)]
+    ///Creates new instance
+    pub fn new(body: T) -> Self {
+        assert!(W > 0, "Window size cannot be zero");
+        Self {
+            body,
+            buffer: bytes::BytesMut::new(),
+            received: 0,
+            is_finished: false,
+        }
+    }
+
+    #[inline(always)]
+    ///Returns number of bytes received from body so far
+    pub const fn received(&self) -> u64 {
+        self.received
+    }
+}
+
+impl<E, T: HttpBody<Data = bytes::Bytes, Error = E>, const W: usize, const S: usize> CollectWindows<W, S, T> {
+    ///Polls for next window, returning `None` once body is exhausted.
+    ///
+    ///On error, stream is terminated.
+    pub fn poll_next(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Option<Result<bytes::Bytes, CollectError<E, core::convert::Infallible>>>> {
+        project!(self => CollectWindows { #[pin] body, buffer, received, is_finished });
+
+        loop {
+            if buffer.len() >= W {
+                break task::Poll::Ready(Some(Ok(buffer.split_to(W).freeze())));
+            } else if *is_finished {
+                break match buffer.is_empty() {
+                    true => task::Poll::Ready(None),
+                    false => task::Poll::Ready(Some(Ok(buffer.split().freeze()))),
+                };
+            }
+
+            match HttpBody::poll_frame(body.as_mut(), ctx) {
+                task::Poll::Ready(Some(Ok(frame))) => if let Ok(data) = frame.into_data() {
+                    *received = received.saturating_add(data.len() as u64);
+                    if *received > S as u64 {
+                        *is_finished = true;
+                        buffer.clear();
+                        break task::Poll::Ready(Some(Err(CollectError::Overflow(None))));
+                    }
+                    buffer.extend_from_slice(&data);
+                },
+                task::Poll::Ready(Some(Err(error))) => {
+                    *is_finished = true;
+                    buffer.clear();
+                    break task::Poll::Ready(Some(Err(CollectError::Transport(error))));
+                },
+                task::Poll::Ready(None) => *is_finished = true,
+                task::Poll::Pending => break task::Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<E, T: HttpBody<Data = bytes::Bytes, Error = E>, const W: usize, const S: usize> futures_core::Stream for CollectWindows<W, S, T> {
+    type Item = Result<bytes::Bytes, CollectError<E, core::convert::Infallible>>;
+
+    #[inline(always)]
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut task::Context<'_>) -> task::Poll<Option<Self::Item>> {
+        CollectWindows::poll_next(self, ctx)
+    }
+}
@@ -0,0 +1,65 @@
+use std::io::{self, Write};
+
+use super::Collector;
+
+///Collector, that writes body into `std::io::Write` sink (e.g. file or socket), without keeping it in memory.
+///
+///`len` is total number of bytes written, hence `Collect` limit still applies.
+///On `consume`, writer is flushed and returned.
+pub struct WriteCollector<W> {
+    writer: Option<W>,
+    len: usize,
+}
+
+impl<W: Write> WriteCollector<W> {
+    #[inline(always)]
+    ///Creates new instance
+    pub const fn new(writer: W) -> Self {
+        Self {
+            writer: Some(writer),
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn consumed() -> io::Error {
+        io::Error::other("WriteCollector is already consumed")
+    }
+}
+
+impl<W: Write + Unpin> Collector for WriteCollector<W> {
+    type Output = W;
+    type Error = io::Error;
+
+    fn append(&mut self, data: bytes::Bytes) -> Option<Self::Error> {
+        let writer = match self.writer.as_mut() {
+            Some(writer) => writer,
+            None => return Some(Self::consumed()),
+        };
+        match writer.write_all(&data) {
+            Ok(()) => {
+                self.len = self.len.saturating_add(data.len());
+                None
+            },
+            Err(error) => Some(error),
+        }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    fn on_trailers(&mut self, _: http::HeaderMap) {
+    }
+
+    fn consume(&mut self) -> Result<Self::Output, Self::Error> {
+        let mut writer = match self.writer.take() {
+            Some(writer) => writer,
+            None => return Err(Self::consumed()),
+        };
+        writer.flush()?;
+        Ok(writer)
+    }
+}
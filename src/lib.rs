@@ -1,7 +1,7 @@
 //!Fancy HTTP utilities aimed at `hyper`
 
 #![no_std]
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::style))]
+#![allow(clippy::style)]
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![warn(missing_docs)]
 
@@ -19,4 +19,55 @@ macro_rules! unreach {
     }}
 }
 
+//Projects pinned struct `$this` onto its fields, binding `#[pin]` fields as `Pin<&mut _>` and the rest as `&mut _`.
+//
+//Prefer `pin_project_lite::pin_project!`, this is only for types with const generics, which it cannot parse.
+//
+//Invariants, that must hold for `$ty` to use it:
+//- `$ty` has no `Drop` implementation, as `Drop::drop` gets `&mut Self` and could move pinned field;
+//- `$ty` is `Unpin` only if all `#[pin]` fields are;
+//- `#[pin]` fields are never moved out of `Pin<&mut $ty>`, which holds as long as they are accessed only via this macro.
+//Fields without `#[pin]` are never pinned, hence can be moved freely.
+//
+//First two are enforced by `pin_invariants!`, which must be invoked for every such type.
+macro_rules! project {
+    ($this:expr => $ty:ident { $($(#[$attr:ident])? $field:ident),+ $(,)? }) => {
+        //SAFETY: nothing is moved out of `$this`, only its fields are borrowed
+        let $ty { $($field,)+ .. } = unsafe {
+            core::pin::Pin::get_unchecked_mut($this)
+        };
+        $(
+            project!(@field $(#[$attr])? $field);
+        )+
+    };
+    (@field #[pin] $field:ident) => {
+        //SAFETY: field is structurally pinned, as it is part of pinned `$this`, which upholds invariants above
+        #[allow(unused_mut)]
+        let mut $field = unsafe {
+            core::pin::Pin::new_unchecked($field)
+        };
+    };
+    (@field $field:ident) => {
+    };
+}
+
+//Enforces invariants of `project!` for `$ty`, where `$pinned` are types of its `#[pin]` fields:
+//- `Drop` implementation of `$ty` fails to compile, as it conflicts with blanket implementation of `MustNotImplDrop`;
+//- `Unpin` is implemented only when `$pinned` are `Unpin`, so that any other `Unpin` implementation fails to compile as conflicting.
+macro_rules! pin_invariants {
+    (impl[$($generics:tt)*] $ty:ty where $($pinned:ty: Unpin),+ $(,)?) => {
+        const _: () = {
+            #[allow(dead_code)]
+            trait MustNotImplDrop {}
+            #[allow(drop_bounds)]
+            impl<T: Drop> MustNotImplDrop for T {}
+            impl<$($generics)*> MustNotImplDrop for $ty {}
+        };
+
+        impl<$($generics)*> Unpin for $ty where $($pinned: Unpin),+ {}
+    };
+}
+
 pub mod body;
+#[cfg(feature = "hash")]
+pub mod hash;
@@ -45,6 +45,55 @@ fn call_future_once<T: Future + Unpin>(mut fut: T) -> T::Output {
     }
 }
 
+#[track_caller]
+fn poll_frames<T: http_fancy::body::HttpBody + Unpin>(mut body: T) -> Vec<http_fancy::body::Frame<T::Data>> where T::Error: core::fmt::Debug {
+    let location = core::panic::Location::caller();
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    let mut frames = Vec::new();
+    loop {
+        match http_fancy::body::HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+            task::Poll::Ready(Some(Ok(frame))) => frames.push(frame),
+            task::Poll::Ready(Some(Err(error))) => panic!("unexpected error from {location}: {error:?}"),
+            task::Poll::Ready(None) => break frames,
+            task::Poll::Pending => panic!("unexpected pending from {location}"),
+        }
+    }
+}
+
+struct FramesBody {
+    frames: std::collections::VecDeque<bytes::Bytes>,
+    trailers: Option<http::HeaderMap>,
+}
+
+impl FramesBody {
+    fn new(frames: &[&'static [u8]]) -> Self {
+        Self {
+            frames: frames.iter().map(|frame| bytes::Bytes::from_static(frame)).collect(),
+            trailers: None,
+        }
+    }
+
+    #[allow(unused)]
+    fn with_trailers(mut self, trailers: http::HeaderMap) -> Self {
+        self.trailers = Some(trailers);
+        self
+    }
+}
+
+impl http_fancy::body::HttpBody for FramesBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+        match self.frames.pop_front() {
+            Some(data) => task::Poll::Ready(Some(Ok(http_fancy::body::Frame::data(data)))),
+            None => task::Poll::Ready(self.trailers.take().map(|trailers| Ok(http_fancy::body::Frame::trailers(trailers)))),
+        }
+    }
+}
+
 #[test]
 fn should_collect_small_body() {
     let body = "12".to_owned();
@@ -60,7 +109,7 @@ fn should_overflow_on_limit() {
     let body = "12".to_owned();
     let result = Collect::<1, _, _>::new(body, Vec::new());
     match call_future_once(result) {
-        Err(CollectError::Overflow) => (),
+        Err(CollectError::Overflow(_)) => (),
         Err(error) => panic!("Unexpected error: {error}"),
         Ok(data) => panic!("Unexpected result: {:?}", data),
     }
@@ -77,3 +126,3313 @@ fn should_decompress_zstd() {
         Err(error) => panic!("Unexpected error: {error}"),
     }
 }
+
+#[cfg(feature = "hash")]
+#[test]
+fn should_compute_etag_alongside_body() {
+    use http_fancy::body::{Body, ETagKind};
+    use http_fancy::hash::Sha256;
+
+    let (body, etag) = Body::with_etag(bytes::Bytes::from_static(b"abc"));
+    assert_eq!(etag, "\"ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=\"");
+    match call_future_once(Collect::<3, _, _>::new(body, Vec::new())) {
+        Ok(data) => assert_eq!(data, b"abc"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let (_, etag) = Body::with_etag_by::<Sha256>(bytes::Bytes::from(vec![b'a'; 200]), ETagKind::Weak);
+    assert_eq!(etag, "W/\"wqkI2Y9d+Yet5BtfziEwZ++8wh7yJAISpB5UtefCiuU=\"");
+}
+
+struct UnderDeliveringBody {
+    data: Option<bytes::Bytes>,
+    lower: u64,
+}
+
+impl http_fancy::body::HttpBody for UnderDeliveringBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+        task::Poll::Ready(self.data.take().map(|data| Ok(http_fancy::body::Frame::data(data))))
+    }
+
+    fn size_hint(&self) -> http_fancy::body::SizeHint {
+        let mut hint = http_fancy::body::SizeHint::new();
+        hint.set_lower(self.lower);
+        hint
+    }
+}
+
+#[test]
+fn should_detect_truncated_body_by_lower_bound() {
+    let body = UnderDeliveringBody {
+        data: Some(bytes::Bytes::from_static(b"12")),
+        lower: 4,
+    };
+    let result = Collect::<10, _, _>::new(body, Vec::new()).expect_lower_bound();
+    match call_future_once(result) {
+        Err(CollectError::Truncated { expected_min: 4, actual: 2 }) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body = UnderDeliveringBody {
+        data: Some(bytes::Bytes::from_static(b"1234")),
+        lower: 4,
+    };
+    let result = Collect::<10, _, _>::new(body, Vec::new()).expect_lower_bound();
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"1234"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_count_byte_occurrences() {
+    let body = "line1\nline2\n\nline4".to_owned();
+    let result = Collect::<100, _, _>::new(body, http_fancy::body::CountCollector::new(b'\n'));
+    match call_future_once(result) {
+        Ok(count) => assert_eq!(count, 3),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = "\n\n\n".to_owned();
+    let result = Collect::<2, _, _>::new(body, http_fancy::body::CountCollector::new(b'\n'));
+    match call_future_once(result) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(count) => panic!("Unexpected result: {count}"),
+    }
+}
+
+#[test]
+fn should_carry_extensions_alongside_body() {
+    #[derive(Debug, PartialEq)]
+    struct RequestId(u64);
+
+    let mut body = http_fancy::body::BodyWithExtensions::new(http_fancy::body::Body::from("12"));
+    body.extensions_mut().insert(RequestId(5));
+    assert_eq!(body.extensions().get::<RequestId>(), Some(&RequestId(5)));
+
+    let result = Collect::<2, _, _>::new(body, Vec::new());
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"12"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_restore_decompress_collector_from_snapshot() {
+    use http_fancy::body::{Collector, DecompressCollector, DecompressSnapshotContent, DecompressError};
+
+    let mut collector = DecompressCollector::new();
+    assert!(collector.append(bytes::Bytes::from_static(b"12")).is_none());
+    let snapshot = collector.snapshot().expect("to snapshot");
+    assert_eq!(snapshot.content(), &DecompressSnapshotContent::Undetected(b"12".to_vec()));
+
+    let mut collector = DecompressCollector::restore(snapshot);
+    assert!(collector.append(bytes::Bytes::from_static(b"345")).is_none());
+    let snapshot = collector.snapshot().expect("to snapshot");
+    assert_eq!(snapshot.content(), &DecompressSnapshotContent::Identity(b"12345".to_vec()));
+    assert_eq!(snapshot.as_bytes(), b"12345");
+
+    let result = Collect::<100, _, _>::new("678".to_owned(), DecompressCollector::restore(snapshot));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"12345678"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut collector = DecompressCollector::new();
+    let compressed = zstd::bulk::compress(b"123456789", 9).expect("To encode");
+    assert!(collector.append(compressed.into()).is_none());
+    match collector.snapshot() {
+        Err(DecompressError::SnapshotUnsupported) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(snapshot) => panic!("Unexpected snapshot: {:?}", snapshot),
+    }
+}
+
+struct PendingBody;
+
+impl http_fancy::body::HttpBody for PendingBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    fn poll_frame(self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+        task::Poll::Pending
+    }
+}
+
+#[test]
+fn should_limit_poll_count() {
+    use http_fancy::body::{HttpBody, PollLimitBody, PollLimitError};
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    let mut body = PollLimitBody::new(PendingBody, 2);
+
+    for _ in 0..2 {
+        assert!(Pin::new(&mut body).poll_frame(&mut ctx).is_pending());
+    }
+    match Pin::new(&mut body).poll_frame(&mut ctx) {
+        task::Poll::Ready(Some(Err(PollLimitError::PollLimitExceeded))) => (),
+        task::Poll::Ready(Some(Err(error))) => panic!("Unexpected error: {error}"),
+        task::Poll::Ready(Some(Ok(_))) => panic!("Unexpected frame"),
+        task::Poll::Ready(None) => panic!("Unexpected end of body"),
+        task::Poll::Pending => panic!("Unexpected pending"),
+    }
+    assert_eq!(body.polls(), 2);
+
+    let frames = poll_frames(PollLimitBody::new(http_fancy::body::Body::from("12"), 2));
+    assert_eq!(frames.len(), 1);
+}
+
+#[test]
+fn should_collect_into_rope() {
+    use bytes::Buf;
+    use http_fancy::body::{Collector, RopeCollector};
+
+    let mut collector = RopeCollector::new();
+    assert!(collector.append(bytes::Bytes::from_static(b"123")).is_none());
+    assert!(collector.append(bytes::Bytes::from_static(b"")).is_none());
+    assert!(collector.append(bytes::Bytes::from_static(b"45")).is_none());
+    assert!(collector.append(bytes::Bytes::from_static(b"6789")).is_none());
+    assert_eq!(collector.len(), 9);
+
+    let mut rope = collector.consume().expect("to consume");
+    assert_eq!(rope.segments().count(), 3);
+    assert_eq!(rope.chunk(), b"123");
+    rope.advance(4);
+    assert_eq!(rope.remaining(), 5);
+    assert_eq!(rope.chunk(), b"5");
+    assert_eq!(rope.copy_to_bytes(3), b"567"[..]);
+    assert_eq!(rope.copy_to_bytes(2), b"89"[..]);
+    assert!(rope.is_empty());
+
+    let result = Collect::<9, _, _>::new("123456789".to_owned(), RopeCollector::new());
+    match call_future_once(result) {
+        Ok(mut rope) => assert_eq!(rope.copy_to_bytes(9), b"123456789"[..]),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_decode_text_by_content_type_charset() {
+    use http_fancy::body::{content_type_charset, Charset, TextCollector};
+
+    let content_type = http::HeaderValue::from_static("text/plain; format=flowed; Charset=\"ISO-8859-1\"");
+    assert_eq!(content_type_charset(&content_type), Some("ISO-8859-1"));
+    assert_eq!(Charset::from_content_type(&content_type), Some(Charset::Latin1));
+    assert_eq!(Charset::from_content_type(&http::HeaderValue::from_static("text/plain")), None);
+    assert_eq!(Charset::from_content_type(&http::HeaderValue::from_static("text/plain;charset=koi8-r")), None);
+
+    let result = Collect::<10, _, _>::new(http_fancy::body::Body::from(vec![b'c', b'a', b'f', 0xE9]), TextCollector::from_content_type(&content_type).expect("supported charset"));
+    match call_future_once(result) {
+        Ok(text) => assert_eq!(text, "café"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let content_type = http::HeaderValue::from_static("application/json; charset=utf-16");
+    let result = Collect::<10, _, _>::new(http_fancy::body::Body::from(vec![0xFF, 0xFE, b'o', 0, b'k', 0]), TextCollector::from_content_type(&content_type).expect("supported charset"));
+    match call_future_once(result) {
+        Ok(text) => assert_eq!(text, "ok"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    assert!(TextCollector::from_content_type(&http::HeaderValue::from_static("text/plain;charset=koi8-r")).is_none());
+    let collector = TextCollector::from_content_type(&http::HeaderValue::from_static("text/plain")).expect("default charset");
+    assert_eq!(collector.charset(), Charset::Utf8);
+
+    let result = Collect::<10, _, _>::new(http_fancy::body::Body::from(vec![0xFF, 0xFE]), TextCollector::new(Charset::Utf8));
+    match call_future_once(result) {
+        Err(CollectError::ConsumeError(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(text) => panic!("Unexpected result: {text}"),
+    }
+}
+
+#[test]
+fn should_collect_into_borrowed_buffer() {
+    let mut buffer = Vec::with_capacity(4);
+    let result = Collect::<4, _, _>::new("12".to_owned(), &mut buffer);
+    match call_future_once(result) {
+        Ok(()) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let result = Collect::<4, _, _>::new("34".to_owned(), &mut buffer);
+    match call_future_once(result) {
+        Ok(()) => assert_eq!(buffer, b"1234"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let result = Collect::<4, _, _>::new("5".to_owned(), &mut buffer);
+    match call_future_once(result) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(()) => panic!("Unexpected success"),
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn should_format_sse_events() {
+    use http_fancy::body::{SseBody, SseError};
+
+    let (sender, body) = SseBody::channel();
+    sender.send_event("update", "line1\r\nline2").expect("to send");
+    sender.send_data("ping").expect("to send");
+    sender.send_data("x\revent: evil\rid: 1\n").expect("to send");
+    assert_eq!(sender.send_event("bad\nname", "data"), Err(SseError::InvalidEventName));
+    assert_eq!(sender.send_event("bad\rname", "data"), Err(SseError::InvalidEventName));
+    drop(sender);
+
+    let result = Collect::<200, _, _>::new(body, Vec::new());
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"event: update\ndata: line1\ndata: line2\n\ndata: ping\n\ndata: x\ndata: event: evil\ndata: id: 1\ndata: \n\n"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let (sender, body) = SseBody::channel();
+    drop(body);
+    assert!(sender.is_closed());
+    assert_eq!(sender.send_data("data"), Err(SseError::Closed));
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn should_spill_large_body_to_disk() {
+    use std::io::Read;
+    use http_fancy::body::{SpillCollector, Spilled};
+
+    let result = Collect::<100, _, _>::new("1234".to_owned(), SpillCollector::new(4));
+    match call_future_once(result) {
+        Ok(Spilled::Memory(data)) => assert_eq!(data, b"1234"[..]),
+        Ok(Spilled::File(_)) => panic!("Unexpected spill"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let result = Collect::<100, _, _>::new("12345".to_owned(), SpillCollector::new(4));
+    let mut spilled = match call_future_once(result) {
+        Ok(Spilled::File(spilled)) => spilled,
+        Ok(Spilled::Memory(_)) => panic!("Unexpected in-memory body"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    };
+    let path = spilled.path().to_owned();
+    let mut data = String::new();
+    spilled.file().read_to_string(&mut data).expect("to read");
+    assert_eq!(data, "12345");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(spilled.file().metadata().expect("metadata").permissions().mode() & 0o777, 0o600);
+    }
+
+    drop(spilled);
+    assert!(!path.exists());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn should_fail_collect_below_min_rate() {
+    use core::time::Duration;
+    use http_fancy::body::CollectMinRate;
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+
+    let mut result = CollectMinRate::new(Collect::<10, _, _>::new(PendingBody, Vec::new()), 1000, Duration::from_millis(10));
+    assert!(Future::poll(Pin::new(&mut result), &mut ctx).is_pending());
+    std::thread::sleep(Duration::from_millis(20));
+    match Future::poll(Pin::new(&mut result), &mut ctx) {
+        task::Poll::Ready(Err(CollectError::TooSlow { received: 0, elapsed })) => assert!(elapsed >= Duration::from_millis(20)),
+        task::Poll::Ready(Err(error)) => panic!("Unexpected error: {error}"),
+        task::Poll::Ready(Ok(data)) => panic!("Unexpected result: {:?}", data),
+        task::Poll::Pending => panic!("Unexpected pending"),
+    }
+
+    let result = CollectMinRate::new(Collect::<10, _, _>::new("12".to_owned(), Vec::new()), 1_000_000, Duration::ZERO);
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"12"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn should_emit_checksum_trailers() {
+    use http_fancy::body::{Body, HashTrailersBody, HttpBody, CHECKSUM_SHA256};
+
+    let body = HashTrailersBody::new(Body::from("123456789"));
+    assert!(!body.is_end_stream());
+    assert_eq!(body.size_hint().exact(), None);
+    assert_eq!(body.size_hint().lower(), 9);
+    let mut frames = poll_frames(body).into_iter();
+    assert_eq!(frames.next().expect("data frame").into_data().expect("data"), b"123456789"[..]);
+    let trailers = frames.next().expect("trailers frame").into_trailers().expect("trailers");
+    assert_eq!(trailers[CHECKSUM_SHA256], "15e2b0d3c33891ebb0f1ef609ec419420c20e320ce94c65fbc8c3312448eb225");
+    assert!(frames.next().is_none());
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_fail_decompress_on_required_encoding_mismatch() {
+    use http_fancy::body::{DecompressCollector, DecompressError, Encoding};
+
+    let result = Collect::<100, _, _>::new("123456789".to_owned(), DecompressCollector::new().require(Encoding::Zstd));
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::EncodingMismatch { expected: Encoding::Zstd, actual: Encoding::Identity })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let result = Collect::<100, _, _>::new("1".to_owned(), DecompressCollector::new().require(Encoding::Zstd));
+    match call_future_once(result) {
+        Err(CollectError::ConsumeError(DecompressError::EncodingMismatch { expected: Encoding::Zstd, actual: Encoding::Identity })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body: http_fancy::body::Body = zstd::bulk::compress(b"123456789", 9).expect("To encode").into();
+    let result = Collect::<100, _, _>::new(body, DecompressCollector::new().require(Encoding::Identity));
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::EncodingMismatch { expected: Encoding::Identity, actual: Encoding::Zstd })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body: http_fancy::body::Body = zstd::bulk::compress(b"123456789", 9).expect("To encode").into();
+    let result = Collect::<100, _, _>::new(body, DecompressCollector::new().require(Encoding::Zstd));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"123456789"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn should_decompress_gzip_across_frames() {
+    use http_fancy::body::{DecompressCollector, DecompressError, Encoding};
+
+    const GZIP: [u8; 46] = [
+        31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 203, 72, 205, 201, 201, 87, 200, 64, 34, 211, 171, 50, 11, 20, 202, 243, 139, 114, 82, 116, 144, 197,
+        21, 51, 104, 160, 18, 0, 92, 58, 1, 241, 126, 0, 0, 0,
+    ];
+    let expected = "hello hello hello gzip world, hello hello!".repeat(3);
+
+    let body = FramesBody::new(&[&GZIP[..3], &GZIP[3..20], &GZIP[20..40], &GZIP[40..]]);
+    let result = Collect::<200, _, _>::new(body, DecompressCollector::new().require(Encoding::Gzip));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, expected.as_bytes()),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    //Multiple members, fed byte by byte
+    let members: &'static [u8] = [GZIP, GZIP].concat().leak();
+    let frames: &'static [&'static [u8]] = members.chunks(1).collect::<Vec<_>>().leak();
+    match call_future_once(Collect::<1000, _, _>::new(FramesBody::new(frames), DecompressCollector::new())) {
+        Ok(data) => assert_eq!(data, expected.repeat(2).as_bytes()),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[&GZIP[..GZIP.len() - 1]]);
+    match call_future_once(Collect::<200, _, _>::new(body, DecompressCollector::new())) {
+        Err(CollectError::ConsumeError(DecompressError::Gzip(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let mut corrupted = GZIP;
+    corrupted[38] ^= 1;
+    let corrupted: &'static [u8] = corrupted.to_vec().leak();
+    let body = FramesBody::new(&[corrupted]);
+    let result = Collect::<200, _, _>::new(body, DecompressCollector::new());
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Gzip(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_validate_json_across_frames() {
+    use http_fancy::body::{JsonError, JsonValidateCollector};
+
+    let body = FramesBody::new(&[b" {\"ke", b"y\": [1, -2.5e+1", b"0, tr", b"ue, null, \"\\u00e9\xc3", b"\xa9\"], \"o\": {}} "]);
+    let result = Collect::<100, _, _>::new(body, JsonValidateCollector::new());
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, " {\"key\": [1, -2.5e+10, true, null, \"\\u00e9\u{e9}\"], \"o\": {}} ".as_bytes()),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let result = Collect::<100, _, _>::new("42".to_owned(), JsonValidateCollector::new());
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"42"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[b"[1, 2", b",]"]);
+    let result = Collect::<100, _, _>::new(body, JsonValidateCollector::new());
+    match call_future_once(result) {
+        Err(CollectError::AppendError(JsonError::Syntax { offset: 6 })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let result = Collect::<100, _, _>::new("{\"key\": 01}".to_owned(), JsonValidateCollector::new());
+    match call_future_once(result) {
+        Err(CollectError::AppendError(JsonError::Syntax { offset: 9 })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let result = Collect::<100, _, _>::new("{\"key\": [tru".to_owned(), JsonValidateCollector::new());
+    match call_future_once(result) {
+        Err(CollectError::ConsumeError(JsonError::Incomplete)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn should_deserialize_json_across_frames() {
+    use std::collections::BTreeMap;
+    use http_fancy::body::JsonCollector;
+
+    let body = FramesBody::new(&[b"{\"a\": [1,", b" 2], \"b\"", b": []}"]);
+    match call_future_once(Collect::<100, _, _>::new(body, JsonCollector::<BTreeMap<String, Vec<u32>>>::new())) {
+        Ok(data) => {
+            assert_eq!(data["a"], [1, 2]);
+            assert!(data["b"].is_empty());
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[b"[1, ", b"-2]"]);
+    match call_future_once(Collect::<100, _, _>::new(body, JsonCollector::<Vec<u32>>::new())) {
+        Err(CollectError::ConsumeError(error)) => assert!(error.is_data()),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body = FramesBody::new(&[b"[1, ", b"2"]);
+    match call_future_once(Collect::<100, _, _>::new(body, JsonCollector::<Vec<u32>>::new())) {
+        Err(CollectError::ConsumeError(error)) => assert!(error.is_eof()),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body = FramesBody::new(&[b"[1, ", b"2, 3]"]);
+    match call_future_once(Collect::<6, _, _>::new(body, JsonCollector::<Vec<u32>>::new())) {
+        Err(CollectError::Overflow(None)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_report_decompress_progress_and_abort() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use http_fancy::body::{DecompressCollector, DecompressError, DecompressProgressCollector};
+
+    //Every zstd frame is sent in its own body frame
+    let compressed: &'static [u8] = zstd::bulk::compress(&[b'a'; 4096], 9).expect("To encode").leak();
+    let frames = [compressed, compressed, compressed];
+
+    let mut progress = Vec::new();
+    let collector = DecompressProgressCollector::new(DecompressCollector::new(), Arc::new(AtomicBool::new(false)), |input, output| progress.push((input, output)));
+    let result = Collect::<16384, _, _>::new(FramesBody::new(&frames), collector);
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, [b'a'; 4096 * 3]),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert_eq!(progress.len(), 3);
+    assert_eq!(progress[2].0, compressed.len() * 3);
+    assert!(progress.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+
+    let abort = Arc::new(AtomicBool::new(false));
+    let ratio_abort = abort.clone();
+    let collector = DecompressProgressCollector::new(DecompressCollector::new(), abort, move |input, output| if output > input * 10 {
+        ratio_abort.store(true, Ordering::Release);
+    });
+    let result = Collect::<16384, _, _>::new(FramesBody::new(&frames), collector);
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Cancelled)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data.len()),
+    }
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_decompress_by_content_encoding() {
+    use http::HeaderValue;
+    use http_fancy::body::{DecompressCollector, DecompressError};
+
+    let compressed: &'static [u8] = zstd::bulk::compress(b"123456789", 9).expect("To encode").leak();
+
+    //Identity is never sniffed
+    let collector = DecompressCollector::from_content_encoding(&HeaderValue::from_static("identity")).expect("supported");
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[compressed]), collector);
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, compressed),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    for encoding in ["ZSTD", "unknown"] {
+        let collector = DecompressCollector::from_content_encoding(&HeaderValue::from_static(encoding)).expect("supported");
+        let result = Collect::<100, _, _>::new(FramesBody::new(&[compressed]), collector);
+        match call_future_once(result) {
+            Ok(data) => assert_eq!(data, b"123456789"),
+            Err(error) => panic!("Unexpected error: {error}"),
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    {
+        const ZLIB: [u8; 19] = [120, 218, 75, 73, 77, 203, 73, 44, 73, 85, 72, 65, 165, 21, 1, 113, 152, 8, 225];
+        let collector = DecompressCollector::from_content_encoding(&HeaderValue::from_static("deflate")).expect("supported");
+        let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZLIB[..5], &ZLIB[5..]]), collector);
+        match call_future_once(result) {
+            Ok(data) => assert_eq!(data, b"deflate deflate deflate!"),
+            Err(error) => panic!("Unexpected error: {error}"),
+        }
+    }
+
+    for encoding in ["br", "gzip, zstd"] {
+        match DecompressCollector::from_content_encoding(&HeaderValue::from_static(encoding)) {
+            Err(DecompressError::UnsupportedEncoding(actual)) => assert_eq!(actual, encoding),
+            Err(error) => panic!("Unexpected error: {error}"),
+            Ok(_) => panic!("Unexpected success for {encoding}"),
+        }
+    }
+}
+
+#[test]
+fn should_tap_every_data_frame() {
+    use http_fancy::body::{Body, TapBody};
+
+    let mut seen = Vec::new();
+    let body = TapBody::new(Body::from("12345"), |data: &bytes::Bytes| seen.push(data.clone()));
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(seen, [bytes::Bytes::from_static(b"12345")]);
+}
+
+#[test]
+fn should_collect_length_prefixed_messages() {
+    use http_fancy::body::{Collector, FramedCollector, FramedError, LengthPrefix};
+
+    let mut collector = FramedCollector::new(LengthPrefix::U16Be);
+    for chunk in [&b"\x00"[..], b"\x0312", b"3\x00\x00\x00", b"\x01x"] {
+        assert!(collector.append(bytes::Bytes::copy_from_slice(chunk)).is_none());
+    }
+    let messages = collector.consume().expect("to consume");
+    assert_eq!(messages, [&b"123"[..], b"", b"x"]);
+
+    let body = http_fancy::body::Body::from(vec![2, 0, 0, 0, b'o', b'k', 5, 0, 0, 0, b'a']);
+    let result = Collect::<100, _, _>::new(body, FramedCollector::new(LengthPrefix::U32Le));
+    match call_future_once(result) {
+        Err(CollectError::ConsumeError(FramedError::Incomplete { remaining: 5 })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(messages) => panic!("Unexpected result: {:?}", messages),
+    }
+}
+
+#[test]
+fn should_build_not_modified_response() {
+    use http_fancy::body::HttpBody;
+
+    let response = http_fancy::body::not_modified();
+    assert_eq!(response.status(), http::StatusCode::NOT_MODIFIED);
+    assert!(response.headers().get(http::header::CONTENT_LENGTH).is_none());
+    assert!(response.body().is_end_stream());
+}
+
+#[test]
+fn should_limit_line_length() {
+    use http_fancy::body::{Collector, LineError, LineLengthCollector};
+
+    let mut collector = LineLengthCollector::new(Vec::new(), 3);
+    assert!(collector.append(bytes::Bytes::from_static(b"12")).is_none());
+    assert!(collector.append(bytes::Bytes::from_static(b"3\n123\n1")).is_none());
+    match collector.append(bytes::Bytes::from_static(b"234\n")) {
+        Some(LineError::LineTooLong) => (),
+        Some(error) => panic!("Unexpected error: {error}"),
+        None => panic!("Unexpected success"),
+    }
+
+    let result = Collect::<100, _, _>::new("123\n\n12\n".to_owned(), LineLengthCollector::new(Vec::new(), 3));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"123\n\n12\n"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let result = Collect::<100, _, _>::new("1\n1234\n12".to_owned(), LineLengthCollector::new(Vec::new(), 3));
+    match call_future_once(result) {
+        Err(CollectError::AppendError(LineError::LineTooLong)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_strip_decoded_encoding_headers() {
+    use http::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+    use http_fancy::body::{strip_decoded_encodings, strip_encoding_headers};
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert(CONTENT_LENGTH, http::HeaderValue::from_static("10"));
+    headers.append(CONTENT_ENCODING, http::HeaderValue::from_static("gzip"));
+    headers.append(CONTENT_ENCODING, http::HeaderValue::from_static("br, zstd"));
+
+    let mut partial = headers.clone();
+    strip_decoded_encodings(&mut partial, 0);
+    assert_eq!(partial, headers);
+    strip_decoded_encodings(&mut partial, 1);
+    assert!(partial.get(CONTENT_LENGTH).is_none());
+    assert_eq!(partial.get_all(CONTENT_ENCODING).iter().collect::<Vec<_>>(), ["gzip, br"]);
+    strip_decoded_encodings(&mut partial, 5);
+    assert!(partial.is_empty());
+
+    strip_encoding_headers(&mut headers);
+    assert!(headers.is_empty());
+}
+
+#[test]
+fn should_split_body_into_header_and_payload() {
+    use http_fancy::body::{Collector, SplitCollect, SplitCollector, SplitError};
+
+    let mut collector = SplitCollector::<3>::new();
+    assert!(collector.append(bytes::Bytes::from_static(b"1")).is_none());
+    assert!(collector.append(bytes::Bytes::from_static(b"2345")).is_none());
+    assert!(collector.append(bytes::Bytes::from_static(b"6")).is_none());
+    let (head, tail) = collector.consume().expect("to consume");
+    assert_eq!(head, b"123"[..]);
+    assert_eq!(tail, b"456"[..]);
+
+    match call_future_once(SplitCollect::<4, 100, _>::split(http_fancy::body::Body::from("1234"))) {
+        Ok((head, tail)) => {
+            assert_eq!(head, b"1234"[..]);
+            assert!(tail.is_empty());
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(SplitCollect::<4, 100, _>::split(http_fancy::body::Body::from("12"))) {
+        Err(CollectError::ConsumeError(SplitError::TooShort { actual: 2 })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(result) => panic!("Unexpected result: {:?}", result),
+    }
+
+    match call_future_once(SplitCollect::<2, 3, _>::split(http_fancy::body::Body::from("1234"))) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(result) => panic!("Unexpected result: {:?}", result),
+    }
+}
+
+struct FailingBody {
+    error: Option<&'static str>,
+    data: Option<bytes::Bytes>,
+}
+
+impl http_fancy::body::HttpBody for FailingBody {
+    type Data = bytes::Bytes;
+    type Error = &'static str;
+
+    fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+        match self.data.take() {
+            Some(data) => task::Poll::Ready(Some(Ok(http_fancy::body::Frame::data(data)))),
+            None => task::Poll::Ready(self.error.take().map(Err)),
+        }
+    }
+}
+
+#[test]
+fn should_retry_body_before_first_frame() {
+    use http_fancy::body::RetryBody;
+
+    let mut attempts = 0;
+    let factory = || {
+        attempts += 1;
+        match attempts {
+            1 | 2 => FailingBody { error: Some("transient"), data: None },
+            _ => FailingBody { error: None, data: Some(bytes::Bytes::from_static(b"ok")) },
+        }
+    };
+    let body = RetryBody::new(factory, |error: &&'static str| *error == "transient", 3);
+    match call_future_once(Collect::<10, _, _>::new(body, Vec::new())) {
+        Ok(data) => assert_eq!(data, b"ok"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let factory = || FailingBody { error: Some("transient"), data: Some(bytes::Bytes::from_static(b"ok")) };
+    let body = RetryBody::new(factory, |error: &&'static str| *error == "transient", 3);
+    match call_future_once(Collect::<10, _, _>::new(body, Vec::new())) {
+        Err(CollectError::Transport("transient")) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let factory = || FailingBody { error: Some("fatal"), data: None };
+    let mut body = RetryBody::new(factory, |error: &&'static str| *error == "transient", 3);
+    match call_future_once(Collect::<10, _, _>::new(&mut body, Vec::new())) {
+        Err(CollectError::Transport("fatal")) => assert_eq!(body.retries(), 0),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_apply_overflow_policy() {
+    use http_fancy::body::{Collector, OverflowPolicy, OverflowPolicyError};
+
+    let result = Collect::<100, _, _>::new("12345".to_owned(), OverflowPolicy::error(Vec::new(), 4));
+    match call_future_once(result) {
+        Err(CollectError::AppendError(OverflowPolicyError::Overflow)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body = FramesBody::new(&[b"123456", b"789", b"abcdef"]);
+    let result = Collect::<10, _, _>::new(body, OverflowPolicy::truncate(Vec::new(), 4));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"1234"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut overflown = None;
+    let result = Collect::<100, _, _>::new("123456".to_owned(), OverflowPolicy::callback(Vec::new(), 4, |len| overflown = Some(len)));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"123456"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert_eq!(overflown, Some(6));
+
+    let mut overflown = Vec::new();
+    let mut collector = OverflowPolicy::callback(Vec::new(), 4, |len| overflown.push(len));
+    for data in [&b"123456"[..], b"1234567"] {
+        assert!(collector.append(bytes::Bytes::from_static(data)).is_none());
+        assert!(collector.is_overflown());
+        match collector.consume() {
+            Ok(result) => assert_eq!(result, data),
+            Err(error) => panic!("Unexpected error: {error}"),
+        }
+        assert!(!collector.is_overflown());
+    }
+    drop(collector);
+    assert_eq!(overflown, [6, 7]);
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn should_verify_body_against_trailer_digest() {
+    use http_fancy::body::{DigestError, DigestVerifyCollector};
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("digest", http::HeaderValue::from_static("md5=abc, SHA-256=FeKw08M4keuw8e9gnsQZQgwg4yDOlMZfvIwzEkSOsiU="));
+    let body = FramesBody::new(&[b"1234", b"56789"]).with_trailers(trailers);
+    match call_future_once(Collect::<100, _, _>::new(body, DigestVerifyCollector::new(Vec::new()))) {
+        Ok(data) => assert_eq!(data, b"123456789"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("content-digest", http::HeaderValue::from_static("sha-256=:FeKw08M4keuw8e9gnsQZQgwg4yDOlMZfvIwzEkSOsiU=:"));
+    let body = FramesBody::new(&[b"1234", b"5678"]).with_trailers(trailers);
+    match call_future_once(Collect::<100, _, _>::new(body, DigestVerifyCollector::new(Vec::new()))) {
+        Err(CollectError::ConsumeError(DigestError::Mismatch)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body = FramesBody::new(&[b"123456789"]);
+    match call_future_once(Collect::<100, _, _>::new(body, DigestVerifyCollector::new(Vec::new()))) {
+        Err(CollectError::ConsumeError(DigestError::Missing)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn should_reset_digest_verify_collector_on_failure() {
+    use http_fancy::body::{Collector, DigestError, DigestVerifyCollector};
+
+    let digest = || {
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("content-digest", http::HeaderValue::from_static("sha-256=:FeKw08M4keuw8e9gnsQZQgwg4yDOlMZfvIwzEkSOsiU=:"));
+        trailers
+    };
+
+    let mut collector = DigestVerifyCollector::new(Vec::new());
+    assert!(collector.append(bytes::Bytes::from_static(b"1234")).is_none());
+    assert!(matches!(collector.consume(), Err(DigestError::Missing)));
+
+    assert!(collector.append(bytes::Bytes::from_static(b"12345678")).is_none());
+    collector.on_trailers(digest());
+    assert!(matches!(collector.consume(), Err(DigestError::Mismatch)));
+
+    assert!(collector.append(bytes::Bytes::from_static(b"123456789")).is_none());
+    collector.on_trailers(digest());
+    match collector.consume() {
+        Ok(data) => assert_eq!(data, b"123456789"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_emit_multi_body_chunks_in_order() {
+    use http_fancy::body::{HttpBody, MultiBody};
+
+    let mut body = MultiBody::new();
+    assert!(body.is_end_stream());
+    body.append(bytes::Bytes::from_static(b"23"));
+    body.append(bytes::Bytes::new());
+    body.prepend(bytes::Bytes::from_static(b"1"));
+    body.append(bytes::Bytes::from_static(b"456"));
+    assert!(!body.is_end_stream());
+    assert_eq!(body.chunks_len(), 3);
+    assert_eq!(body.size_hint().exact(), Some(6));
+
+    let frames = poll_frames(body.clone());
+    let frames = frames.into_iter().map(|frame| frame.into_data().expect("data")).collect::<Vec<_>>();
+    assert_eq!(frames, [&b"1"[..], b"23", b"456"]);
+
+    match call_future_once(Collect::<6, _, _>::new(body, Vec::new())) {
+        Ok(data) => assert_eq!(data, b"123456"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_collect_preview_alongside_full_body() {
+    use http_fancy::body::PreviewCollect;
+
+    match call_future_once(PreviewCollect::<4, 100, _>::preview(FramesBody::new(&[b"%PD", b"F-1.7", b"..."]))) {
+        Ok((preview, full)) => {
+            assert_eq!(preview, b"%PDF"[..]);
+            assert_eq!(full, b"%PDF-1.7..."[..]);
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(PreviewCollect::<4, 100, _>::preview(FramesBody::new(&[b"ab"]))) {
+        Ok((preview, full)) => {
+            assert_eq!(preview, b"ab"[..]);
+            assert_eq!(full, b"ab"[..]);
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "aead")]
+#[test]
+fn should_decrypt_body_verifying_auth_tag() {
+    use http_fancy::body::{Aead, DecryptCollector, DecryptError};
+
+    //Toy cipher: XOR keystream with single byte tag, NOT secure.
+    struct XorCipher(u8);
+
+    impl XorCipher {
+        fn tag(&self, nonce: &[u8], aad: &[u8], data: &[u8]) -> u8 {
+            nonce.iter().chain(aad).chain(data).fold(self.0, |acc, byte| acc.rotate_left(1) ^ byte)
+        }
+
+        fn encrypt(&self, nonce: &[u8], aad: &[u8], plain: &[u8]) -> Vec<u8> {
+            let mut result = plain.iter().map(|byte| byte ^ self.0 ^ nonce[0]).collect::<Vec<_>>();
+            result.push(self.tag(nonce, aad, &result));
+            result
+        }
+    }
+
+    impl Aead for XorCipher {
+        const NONCE_LEN: usize = 2;
+        const TAG_LEN: usize = 1;
+        type Error = &'static str;
+
+        fn decrypt_in_place(&self, nonce: &[u8], aad: &[u8], buffer: &mut Vec<u8>) -> Result<(), Self::Error> {
+            let tag = buffer.pop().expect("tag");
+            if tag != self.tag(nonce, aad, buffer) {
+                return Err("tag mismatch");
+            }
+            for byte in buffer.iter_mut() {
+                *byte ^= self.0 ^ nonce[0];
+            }
+            Ok(())
+        }
+    }
+
+    let ciphertext: &'static [u8] = XorCipher(0x5a).encrypt(b"n1", b"aad", b"secret").leak();
+    let body = FramesBody::new(&[&ciphertext[..3], &ciphertext[3..]]);
+    match call_future_once(Collect::<100, _, _>::new(body, DecryptCollector::new(XorCipher(0x5a), b"n1").with_aad(b"aad"))) {
+        Ok(data) => assert_eq!(data, b"secret"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut prefixed = b"n2".to_vec();
+    prefixed.extend_from_slice(&XorCipher(0x5a).encrypt(b"n2", b"", b"secret"));
+    let prefixed: &'static [u8] = prefixed.leak();
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[prefixed]), DecryptCollector::prefixed(XorCipher(0x5a)))) {
+        Ok(data) => assert_eq!(data, b"secret"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[ciphertext]);
+    match call_future_once(Collect::<100, _, _>::new(body, DecryptCollector::new(XorCipher(0x5a), b"n1"))) {
+        Err(CollectError::ConsumeError(DecryptError::Cipher("tag mismatch"))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"n2"]), DecryptCollector::prefixed(XorCipher(0x5a)))) {
+        Err(CollectError::ConsumeError(DecryptError::TooShort)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_collect_preallocating_from_content_length() {
+    use http_fancy::body::CollectDyn;
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert(http::header::CONTENT_LENGTH, http::HeaderValue::from_static("9"));
+
+    let mut buffer = Vec::new();
+    match call_future_once(Collect::<10, _, _>::from_headers(&headers, FramesBody::new(&[b"1234", b"56789"]), &mut buffer)) {
+        Ok(()) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert_eq!(buffer, b"123456789");
+    assert_eq!(buffer.capacity(), 9);
+
+    match call_future_once(Collect::<8, _, _>::from_headers(&headers, PendingBody, Vec::new())) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    headers.insert(http::header::CONTENT_LENGTH, http::HeaderValue::from_static("garbage"));
+    match call_future_once(Collect::<8, _, _>::from_headers(&headers, FramesBody::new(&[b"1234"]), Vec::new())) {
+        Ok(data) => assert_eq!(data, b"1234"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    headers.insert(http::header::CONTENT_LENGTH, http::HeaderValue::from_static("9"));
+    match call_future_once(CollectDyn::with_limit(PendingBody, Vec::new(), 8).with_headers(&headers)) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    //Declared length is not trusted for preallocation
+    headers.insert(http::header::CONTENT_LENGTH, http::HeaderValue::from_static("18446744073709551615"));
+    let mut result = CollectDyn::from_headers(&headers, FramesBody::new(&[b"1234"]), Vec::new());
+    assert!(result.collector_mut().capacity() <= http_fancy::body::PREALLOCATE_MAX);
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"1234"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_pass_through_frames_with_hook() {
+    use http_fancy::body::{Frame, FrameHook, PassthroughBody};
+
+    let frames = poll_frames(PassthroughBody::new(FramesBody::new(&[b"12", b"345"])));
+    let frames = frames.into_iter().map(|frame| frame.into_data().expect("data")).collect::<Vec<_>>();
+    assert_eq!(frames, [&b"12"[..], b"345"]);
+
+    let body = PassthroughBody::with_hook(FramesBody::new(&[b"a", b"b"]), |index: u64, frame: Frame<bytes::Bytes>| match frame.into_data() {
+        Ok(data) => Frame::data(bytes::Bytes::from(format!("{index}:{}", core::str::from_utf8(&data).unwrap()))),
+        Err(frame) => frame,
+    });
+    let frames = poll_frames(body);
+    let frames = frames.into_iter().map(|frame| frame.into_data().expect("data")).collect::<Vec<_>>();
+    assert_eq!(frames, [&b"0:a"[..], b"1:b"]);
+
+    #[derive(Default)]
+    struct EndCounter(Option<u64>);
+    impl FrameHook for EndCounter {
+        fn on_end(&mut self, frames: u64) {
+            assert!(self.0.is_none());
+            self.0 = Some(frames);
+        }
+    }
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-end", http::HeaderValue::from_static("1"));
+    let mut body = PassthroughBody::with_hook(FramesBody::new(&[b"1", b"2"]).with_trailers(trailers), EndCounter::default());
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    while let task::Poll::Ready(Some(_)) = http_fancy::body::HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+    }
+    assert_eq!(body.frames(), 3);
+    assert_eq!(body.hook().0, Some(3));
+}
+
+#[test]
+fn should_verify_sniffed_content_type() {
+    use http_fancy::body::{sniff, Collector, SniffCollector, SniffError};
+
+    assert_eq!(sniff(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+    assert_eq!(sniff(b"RIFF\x00\x00\x00\x00WEBPVP8 "), Some("image/webp"));
+    assert_eq!(sniff(b"XXXX\x00\x00\x00\x00WEBPVP8 "), None);
+    assert_eq!(sniff(b"hello world"), None);
+    assert_eq!(sniff(b"BM\x36\x00\x0c\x00\x00\x00\x00\x00\x36\x00\x00\x00\x28\x00\x00\x00"), Some("image/bmp"));
+    assert_eq!(sniff(b"BMW owners club meeting"), None);
+    assert_eq!(sniff(b"\x00\x00\x01\x00\x01\x00\x10\x10\x00\x00\x01\x00\x20\x00\x68\x04\x00\x00"), Some("image/x-icon"));
+    assert_eq!(sniff(b"\x00\x00\x01\x00\x00\x00\x10\x10\x00\x00\x01\x00\x20\x00\x68\x04\x00\x00"), None);
+    assert_eq!(sniff(b"ID3\x04\x00\x00\x00\x00\x02\x01TIT2\x00\x00\x00\x05"), Some("audio/mpeg"));
+    assert_eq!(sniff(b"ID3 tags are stored at the start"), None);
+
+    let content_type = http::HeaderValue::from_static("Application/PDF; charset=binary");
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"%PD", b"F-1.7\n%...."]), SniffCollector::from_content_type(&content_type))) {
+        Ok(data) => assert_eq!(data, b"%PDF-1.7\n%...."),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let result = call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"BMW owners", b" club meeting"]), SniffCollector::new("image/bmp")));
+    assert!(matches!(result, Err(CollectError::AppendError(SniffError::Unknown))));
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"\xff\xd8\xff\xe0", b"\x00\x10JFIF\x00\x01\x01", b"......"]), SniffCollector::new("image/png"))) {
+        Err(CollectError::AppendError(SniffError::Mismatch { expected, actual })) => {
+            assert_eq!(expected, "image/png");
+            assert_eq!(actual, "image/jpeg");
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"<svg>"]), SniffCollector::new("image/png"))) {
+        Err(CollectError::ConsumeError(SniffError::Unknown)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let mut collector = SniffCollector::new("image/png");
+    assert!(collector.append(bytes::Bytes::from_static(b"\x89PNG\r\n\x1a\n....")).is_none());
+    assert!(collector.consume().is_ok());
+    assert!(collector.append(bytes::Bytes::from_static(b"<svg>")).is_none());
+    assert!(matches!(collector.consume(), Err(SniffError::Unknown)));
+}
+
+#[test]
+fn should_inspect_collected_output() {
+    let mut inspected = None;
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"12", b"34"]), Vec::new()).inspect(|data| inspected = Some(data.len()))) {
+        Ok(data) => assert_eq!(data, b"1234"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert_eq!(inspected, Some(4));
+
+    let mut inspected = false;
+    match call_future_once(Collect::<2, _, _>::new(FramesBody::new(&[b"12", b"34"]), Vec::new()).inspect(|_| inspected = true)) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+    assert!(!inspected);
+}
+
+#[test]
+fn should_collect_all_bodies_in_order() {
+    use http_fancy::body::CollectAll;
+
+    let bodies = [
+        FramesBody::new(&[b"1", b"2"]),
+        FramesBody::new(&[b"1234"]),
+        FramesBody::new(&[]),
+        FramesBody::new(&[b"abc"]),
+    ];
+    let mut collectors = 0;
+    let results = call_future_once(CollectAll::<3, _, _, _, _>::new(bodies, || {
+        collectors += 1;
+        Vec::new()
+    }, 2));
+    assert_eq!(results.len(), 4);
+    assert!(matches!(&results[0], Ok(data) if data == b"12"));
+    assert!(matches!(&results[1], Err(CollectError::Overflow(_))));
+    assert!(matches!(&results[2], Ok(data) if data.is_empty()));
+    assert!(matches!(&results[3], Ok(data) if data == b"abc"));
+    assert_eq!(collectors, 4);
+
+    let results = call_future_once(CollectAll::<3, FramesBody, _, _, _>::new([], Vec::new, 0));
+    assert!(results.is_empty());
+}
+
+#[test]
+fn should_set_content_length_only_when_known() {
+    use http_fancy::body::{content_length, into_response, Body, MultiBody};
+
+    assert_eq!(content_length(&Body::from("abc")), Some(3));
+    assert_eq!(content_length(&MultiBody::from(vec![bytes::Bytes::from_static(b"12"), bytes::Bytes::from_static(b"3")])), Some(3));
+    assert_eq!(content_length(&PendingBody), None);
+
+    let response = into_response(Body::from("abc"));
+    assert_eq!(response.status(), http::StatusCode::OK);
+    assert_eq!(response.headers()[http::header::CONTENT_LENGTH], "3");
+
+    let response = into_response(PendingBody);
+    assert!(response.headers().get(http::header::CONTENT_LENGTH).is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn should_collect_into_shared_buffer() {
+    use http_fancy::body::{HttpBody, SharedCollector};
+    use std::sync::Arc;
+
+    struct ObservingBody {
+        data: &'static [&'static [u8]],
+        observed: Arc<std::sync::Mutex<Vec<u8>>>,
+        idx: usize,
+    }
+
+    impl HttpBody for ObservingBody {
+        type Data = bytes::Bytes;
+        type Error = core::convert::Infallible;
+
+        fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+            let idx = self.idx;
+            //Everything preceding current frame must be visible
+            let expected = self.data[..idx].concat();
+            assert_eq!(*self.observed.lock().unwrap(), expected);
+            self.idx += 1;
+            task::Poll::Ready(self.data.get(idx).map(|data| Ok(http_fancy::body::Frame::data(bytes::Bytes::from_static(data)))))
+        }
+    }
+
+    let collector = SharedCollector::new();
+    let observed = collector.buffer().clone();
+    let body = ObservingBody {
+        data: &[b"12", b"345"],
+        observed: observed.clone(),
+        idx: 0,
+    };
+    match call_future_once(Collect::<5, _, _>::new(body, collector)) {
+        Ok(data) => {
+            assert!(Arc::ptr_eq(&data, &observed));
+            assert_eq!(*data.lock().unwrap(), b"12345");
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_collect_urlencoded_form() {
+    use http_fancy::body::{parse_form, FormCollector, FormError};
+
+    let body = FramesBody::new(&[b"name=John+Do", b"e&city=S%C3%A3o%20Paulo&&flag&q=a%3Db%26c&bad=100%&x=%zz"]);
+    match call_future_once(Collect::<100, _, _>::new(body, FormCollector::new())) {
+        Ok(form) => assert_eq!(form, [
+            ("name".to_owned(), "John Doe".to_owned()),
+            ("city".to_owned(), "São Paulo".to_owned()),
+            ("flag".to_owned(), "".to_owned()),
+            ("q".to_owned(), "a=b&c".to_owned()),
+            ("bad".to_owned(), "100%".to_owned()),
+            ("x".to_owned(), "%zz".to_owned()),
+        ]),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    assert!(parse_form(b"").unwrap().is_empty());
+    assert!(matches!(parse_form(b"key=%FF"), Err(FormError::InvalidUtf8)));
+}
+
+#[test]
+fn should_block_on_ready_future() {
+    use http_fancy::body::{block_on_ready, Body};
+
+    match block_on_ready(Collect::<100, _, _>::new(Body::from("ready"), Vec::new())) {
+        Some(Ok(data)) => assert_eq!(data, b"ready"),
+        Some(Err(error)) => panic!("Unexpected error: {error}"),
+        None => panic!("Unexpected pending"),
+    }
+
+    assert!(block_on_ready(Collect::<100, _, _>::new(PendingBody, Vec::new())).is_none());
+}
+
+#[test]
+fn should_slice_body_by_range() {
+    use http_fancy::body::Body;
+
+    let (body, range) = Body::from("0123456789").range(2, 4).expect("satisfiable");
+    assert_eq!(range.to_header_value(), "bytes 2-4/10");
+    match call_future_once(Collect::<100, _, _>::new(body, Vec::new())) {
+        Ok(data) => assert_eq!(data, b"234"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let (body, range) = Body::from("0123456789").range(7, u64::MAX).expect("satisfiable");
+    assert_eq!((range.start(), range.end(), range.complete_len()), (7, 9, 10));
+    assert_eq!(http_fancy::body::content_length(&body), Some(3));
+
+    let error = Body::from("0123456789").range(10, 12).expect_err("unsatisfiable");
+    assert_eq!(error.to_header_value(), "bytes */10");
+    assert!(Body::from("0123456789").range(5, 4).is_err());
+    assert!(Body::empty().range(0, 0).is_err());
+}
+
+#[test]
+fn should_collect_into_stable_arena() {
+    use http_fancy::body::{ChunkedArenaCollector, HttpBody};
+
+    let mut collector = ChunkedArenaCollector::<4>::new();
+    let mut body = FramesBody::new(&[b"abc", b"defgh", b"ijklmnop", b"q"]);
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    let mut first = None;
+    while let task::Poll::Ready(Some(Ok(frame))) = HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+        http_fancy::body::Collector::append(&mut collector, frame.into_data().expect("data"));
+        let address = collector.arena().get(0..1).expect("first byte").as_ptr();
+        assert_eq!(*first.get_or_insert(address), address);
+    }
+
+    let arena = http_fancy::body::Collector::consume(&mut collector).expect("arena");
+    assert_eq!(arena.len(), 17);
+    assert_eq!(arena.get(0..4), Some(&b"abcd"[..]));
+    assert_eq!(arena.get(16..17), Some(&b"q"[..]));
+    assert_eq!(arena.get(3..5), None);
+    assert_eq!(arena.get(16..18), None);
+    assert_eq!(arena.byte(9), Some(b'j'));
+    assert_eq!(arena.blocks().collect::<Vec<_>>(), [&b"abcd"[..], b"efgh", b"ijkl", b"mnop", b"q"]);
+
+    let mut out = [0u8; 6];
+    assert_eq!(arena.copy_to(3, &mut out), 6);
+    assert_eq!(&out, b"defghi");
+    assert_eq!(arena.copy_to(15, &mut out), 2);
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"12345"]), ChunkedArenaCollector::<2>::new())) {
+        Ok(arena) => assert_eq!(arena.to_vec(), b"12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_emit_length_check_trailers() {
+    use http_fancy::body::{Body, HttpBody, LengthCheckBody, LENGTH_ACTUAL, LENGTH_MATCH};
+
+    struct ShortBody(Option<bytes::Bytes>);
+
+    impl HttpBody for ShortBody {
+        type Data = bytes::Bytes;
+        type Error = core::convert::Infallible;
+
+        fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+            task::Poll::Ready(self.0.take().map(|data| Ok(http_fancy::body::Frame::data(data))))
+        }
+
+        fn size_hint(&self) -> http_fancy::body::SizeHint {
+            http_fancy::body::SizeHint::with_exact(5)
+        }
+    }
+
+    let body = LengthCheckBody::new(Body::from("abc"));
+    assert_eq!(body.size_hint().exact(), None);
+    assert_eq!(body.size_hint().lower(), 3);
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 2);
+    let trailers = frames[1].trailers_ref().expect("trailers");
+    assert_eq!(trailers[LENGTH_MATCH], "true");
+    assert_eq!(trailers[LENGTH_ACTUAL], "3");
+
+    let body = LengthCheckBody::new(ShortBody(Some(bytes::Bytes::from_static(b"12"))));
+    assert_eq!(body.expected(), Some(5));
+    let frames = poll_frames(body);
+    let trailers = frames[1].trailers_ref().expect("trailers");
+    assert_eq!(trailers[LENGTH_MATCH], "false");
+    assert_eq!(trailers[LENGTH_ACTUAL], "2");
+
+    let mut own = http::HeaderMap::new();
+    own.insert("x-own", http::HeaderValue::from_static("1"));
+    let frames = poll_frames(LengthCheckBody::new(FramesBody::new(&[b"1", b"23"]).with_trailers(own)));
+    assert_eq!(frames.len(), 3);
+    let trailers = frames[2].trailers_ref().expect("trailers");
+    assert_eq!(trailers["x-own"], "1");
+    assert_eq!(trailers[LENGTH_MATCH], "unknown");
+    assert_eq!(trailers[LENGTH_ACTUAL], "3");
+}
+
+#[test]
+fn should_stream_fixed_windows() {
+    use http_fancy::body::CollectWindows;
+
+    fn windows<const W: usize, const S: usize>(body: FramesBody) -> Vec<Result<bytes::Bytes, CollectError<core::convert::Infallible, core::convert::Infallible>>> {
+        let waker = waker::create(should_not_call_waker);
+        let mut ctx = task::Context::from_waker(&waker);
+        let mut windows = CollectWindows::<W, S, _>::new(body);
+        let mut result = Vec::new();
+        while let task::Poll::Ready(Some(window)) = Pin::new(&mut windows).poll_next(&mut ctx) {
+            result.push(window);
+        }
+        result
+    }
+
+    let result = windows::<3, 100>(FramesBody::new(&[b"1", b"2345", b"", b"6789", b"ab"]));
+    let result = result.into_iter().map(|window| window.expect("window")).collect::<Vec<_>>();
+    assert_eq!(result, [&b"123"[..], b"456", b"789", b"ab"]);
+
+    let result = windows::<2, 100>(FramesBody::new(&[b"1234"]));
+    let result = result.into_iter().map(|window| window.expect("window")).collect::<Vec<_>>();
+    assert_eq!(result, [&b"12"[..], b"34"]);
+
+    let result = windows::<2, 3>(FramesBody::new(&[b"12", b"34"]));
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].as_ref().expect("window"), &b"12"[..]);
+    assert!(matches!(result[1], Err(CollectError::Overflow(_))));
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn should_stream_windows_of_not_unpin_body() {
+    use http_fancy::body::{CollectWindows, Stream};
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    let mut windows = core::pin::pin!(CollectWindows::<4, 100, _>::new(PinnedBody::new("pinned")));
+    let mut result = Vec::new();
+    while let task::Poll::Ready(Some(window)) = Stream::poll_next(windows.as_mut(), &mut ctx) {
+        result.push(window.expect("window"));
+    }
+    assert_eq!(result, [&b"pinn"[..], b"ed"]);
+}
+
+#[test]
+fn should_detect_length_mismatch_against_exact_len() {
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"12", b"345"]), Vec::new()).expect_exact_len(5)) {
+        Ok(data) => assert_eq!(data, b"12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"12", b"345", b"6"]), Vec::new()).expect_exact_len(4)) {
+        Err(CollectError::LengthMismatch { expected: 4, actual: 5 }) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"12"]), Vec::new()).expect_exact_len(4)) {
+        Err(CollectError::LengthMismatch { expected: 4, actual: 2 }) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_combine_lower_bound_and_exact_len_checks() {
+    let body = UnderDeliveringBody {
+        data: Some(bytes::Bytes::from_static(b"12")),
+        lower: 4,
+    };
+    match call_future_once(Collect::<10, _, _>::new(body, Vec::new()).expect_lower_bound().expect_exact_len(2)) {
+        Err(CollectError::Truncated { expected_min: 4, actual: 2 }) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body = UnderDeliveringBody {
+        data: Some(bytes::Bytes::from_static(b"12")),
+        lower: 2,
+    };
+    match call_future_once(Collect::<10, _, _>::new(body, Vec::new()).expect_exact_len(3).expect_lower_bound()) {
+        Err(CollectError::LengthMismatch { expected: 3, actual: 2 }) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let mut headers = http::HeaderMap::new();
+    headers.insert(http::header::CONTENT_LENGTH, http::HeaderValue::from_static("20"));
+    let body = UnderDeliveringBody {
+        data: Some(bytes::Bytes::from_static(b"12")),
+        lower: 2,
+    };
+    match call_future_once(Collect::<10, _, _>::new(body, Vec::new()).with_headers(&headers).expect_exact_len(2).expect_lower_bound()) {
+        Err(CollectError::Overflow(None)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_decode_collected_body() {
+    use http_fancy::body::DecodeCollector;
+
+    //MessagePack positive fixint array
+    fn decode_fixarray(data: &[u8]) -> Result<Vec<u8>, &'static str> {
+        match data.split_first() {
+            Some((header, items)) if header & 0xf0 == 0x90 && (header & 0x0f) as usize == items.len() => Ok(items.to_vec()),
+            _ => Err("invalid fixarray"),
+        }
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"\x93\x01", b"\x02\x03"]), DecodeCollector::new(decode_fixarray))) {
+        Ok(items) => assert_eq!(items, [1, 2, 3]),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"\x93\x01"]), DecodeCollector::new(decode_fixarray))) {
+        Err(CollectError::ConsumeError("invalid fixarray")) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(miri, ignore)]
+#[test]
+fn should_throttle_bodies_with_shared_limiter() {
+    use http_fancy::body::{HttpBody, SharedRateLimiter, ThrottledBody};
+    use core::sync::atomic::{AtomicU64, Ordering};
+    use core::time::Duration;
+    use std::sync::{Arc, OnceLock};
+    use std::time::Instant;
+
+    //Time advances only by sleeping
+    static START: OnceLock<Instant> = OnceLock::new();
+    static ELAPSED_NANOS: AtomicU64 = AtomicU64::new(0);
+    fn clock() -> Instant {
+        *START.get_or_init(Instant::now) + Duration::from_nanos(ELAPSED_NANOS.load(Ordering::SeqCst))
+    }
+    fn advance(duration: Duration) {
+        ELAPSED_NANOS.fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+    }
+    fn sleep(duration: Duration) -> core::future::Ready<()> {
+        advance(duration);
+        core::future::ready(())
+    }
+
+    let limiter = Arc::new(SharedRateLimiter::new(1_000, 8).with_quantum(3).with_clock(clock));
+    assert_eq!(limiter.acquire(2), Ok(2));
+    assert_eq!(limiter.acquire(100), Ok(3));
+    assert_eq!(limiter.acquire(100), Ok(3));
+    assert_eq!(limiter.acquire(100), Err(Duration::from_millis(1)));
+    advance(Duration::from_micros(1500));
+    assert_eq!(limiter.acquire(100), Ok(1));
+    assert_eq!(limiter.acquire(100), Err(Duration::from_micros(500)));
+    advance(Duration::from_micros(500));
+
+    let mut sleeps = 0;
+    let mut first = ThrottledBody::new(FramesBody::new(&[b"0123456789"]), limiter.clone(), |duration| {
+        sleeps += 1;
+        sleep(duration)
+    });
+    let mut second = ThrottledBody::new(FramesBody::new(&[b"abcdef"]), limiter, sleep);
+
+    fn poll_chunk<T: HttpBody<Data = bytes::Bytes> + Unpin>(body: &mut T, data: &mut Vec<u8>) -> bool where T::Error: core::fmt::Debug {
+        let waker = waker::create(should_not_call_waker);
+        let mut ctx = task::Context::from_waker(&waker);
+        match HttpBody::poll_frame(Pin::new(body), &mut ctx) {
+            task::Poll::Ready(Some(Ok(frame))) => {
+                let frame = frame.into_data().expect("data");
+                assert!(frame.len() <= 3);
+                data.extend_from_slice(&frame);
+                true
+            },
+            task::Poll::Ready(Some(Err(error))) => panic!("Unexpected error: {error:?}"),
+            task::Poll::Ready(None) => false,
+            task::Poll::Pending => panic!("Unexpected pending"),
+        }
+    }
+
+    let mut first_data = Vec::new();
+    let mut second_data = Vec::new();
+    //Interleave bodies, as they would be polled by separate connections
+    while poll_chunk(&mut first, &mut first_data) | poll_chunk(&mut second, &mut second_data) {
+    }
+
+    assert_eq!(first_data, b"0123456789");
+    assert_eq!(second_data, b"abcdef");
+    drop(first);
+    assert!(sleeps > 0);
+    //Single token was available, the rest of 16 bytes is produced at 1 byte per millisecond
+    assert_eq!(ELAPSED_NANOS.load(Ordering::SeqCst), Duration::from_millis(2 + 15).as_nanos() as u64);
+}
+
+#[test]
+fn should_collect_frame_offsets() {
+    use http_fancy::body::FrameOffsetsCollector;
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"12", b"", b"345", b"6"]), FrameOffsetsCollector::new())) {
+        Ok((data, offsets)) => {
+            assert_eq!(data, b"123456");
+            assert_eq!(offsets, [0, 2, 5]);
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_emit_keep_alive_while_idle() {
+    use http_fancy::body::{HttpBody, KeepAliveBody};
+    use core::time::Duration;
+
+    let mut sleeps = Vec::new();
+    let mut body = KeepAliveBody::new(PendingBody, Duration::from_secs(15), |interval| {
+        sleeps.push(interval);
+        core::future::ready(())
+    });
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    for _ in 0..2 {
+        match HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+            task::Poll::Ready(Some(Ok(frame))) => assert!(frame.into_data().expect("data").is_empty()),
+            _ => panic!("Expected keep-alive frame"),
+        }
+    }
+    drop(body);
+    assert_eq!(sleeps, [Duration::from_secs(15), Duration::from_secs(15)]);
+
+    let mut body = KeepAliveBody::new(PendingBody, Duration::from_secs(15), |_| core::future::pending::<()>());
+    assert!(HttpBody::poll_frame(Pin::new(&mut body), &mut ctx).is_pending());
+
+    let body = KeepAliveBody::new(FramesBody::new(&[b"12", b"3"]), Duration::from_secs(15), |_| core::future::pending::<()>());
+    match call_future_once(Collect::<100, _, _>::new(body, Vec::new())) {
+        Ok(data) => assert_eq!(data, b"123"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut body = KeepAliveBody::new(PendingBody, Duration::from_secs(15), |_| core::future::ready(())).with_payload(bytes::Bytes::from_static(b":\n\n"));
+    match HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+        task::Poll::Ready(Some(Ok(frame))) => assert_eq!(frame.into_data().expect("data"), &b":\n\n"[..]),
+        _ => panic!("Expected keep-alive frame"),
+    }
+
+    let keep_alive = |payload| KeepAliveBody::new(http_fancy::body::Body::from("123"), Duration::from_secs(15), |_| core::future::pending::<()>()).with_payload(payload);
+    assert_eq!(keep_alive(bytes::Bytes::new()).size_hint().exact(), Some(3));
+    let hint = keep_alive(bytes::Bytes::from_static(b":\n\n")).size_hint();
+    assert_eq!((hint.lower(), hint.upper()), (3, None));
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn should_split_into_content_defined_chunks() {
+    use http_fancy::body::ChunkingCollector;
+    use http_fancy::hash::{Digest, Sha256};
+
+    //Deterministic pseudo random content
+    let mut state = 1u32;
+    let data = (0..64 * 1024).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as u8
+    }).collect::<Vec<u8>>();
+    let data: &'static [u8] = data.leak();
+
+    let collect = |frames: Vec<&'static [u8]>| match call_future_once(Collect::<{ 128 * 1024 }, _, _>::new(FramesBody::new(frames.leak()), ChunkingCollector::new(256, 1024, 4096))) {
+        Ok(chunks) => chunks,
+        Err(error) => panic!("Unexpected error: {error}"),
+    };
+
+    let chunks = collect(vec![data]);
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks.iter().map(|(chunk, _)| &chunk[..]).collect::<Vec<_>>().concat(), data);
+    for (chunk, digest) in chunks.iter() {
+        assert!(chunk.len() <= 4096);
+        let mut hasher = Sha256::new();
+        hasher.update(chunk);
+        assert_eq!(hasher.finalize()[..], digest[..]);
+    }
+    for (chunk, _) in &chunks[..chunks.len() - 1] {
+        assert!(chunk.len() >= 256);
+    }
+
+    //Boundaries do not depend on framing
+    let reframed = collect(vec![&data[..7], &data[7..5000], &data[5000..5001], &data[5001..]]);
+    assert_eq!(reframed, chunks);
+
+    //Prepending data only affects leading chunks
+    let mut shifted = b"inserted prefix".to_vec();
+    shifted.extend_from_slice(data);
+    let shifted = collect(vec![shifted.leak()]);
+    let common = chunks.iter().filter(|(_, digest)| shifted.iter().any(|(_, shifted)| shifted == digest)).count();
+    assert!(common >= chunks.len() - 2);
+}
+
+#[test]
+fn should_call_before_first_frame_once() {
+    use http_fancy::body::HttpBody;
+
+    struct OrderBody<'a> {
+        calls: &'a core::cell::Cell<u32>,
+        data: Option<bytes::Bytes>,
+    }
+
+    impl HttpBody for OrderBody<'_> {
+        type Data = bytes::Bytes;
+        type Error = core::convert::Infallible;
+
+        fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+            assert_eq!(self.calls.get(), 1, "callback must be called before polling body");
+            task::Poll::Ready(self.data.take().map(|data| Ok(http_fancy::body::Frame::data(data))))
+        }
+    }
+
+    let calls = core::cell::Cell::new(0);
+    let body = OrderBody {
+        calls: &calls,
+        data: Some(bytes::Bytes::from_static(b"continue")),
+    };
+    let collect = Collect::<100, _, _>::new(body, Vec::new()).before_first_frame(|| calls.set(calls.get() + 1));
+    match call_future_once(collect) {
+        Ok(data) => assert_eq!(data, b"continue"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn should_serve_embedded_assets() {
+    use http_fancy::body::AssetMap;
+
+    static ASSETS: AssetMap = AssetMap::new(&[
+        ("/app.js", b"alert(1)"),
+        ("/index.html", b"<html></html>"),
+        ("/style.css", b"body {}"),
+    ]);
+
+    let body = ASSETS.get("/index.html").expect("asset");
+    assert_eq!(http_fancy::body::content_length(&body), Some(13));
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 1);
+    let data = frames[0].data_ref().expect("data");
+    assert_eq!(data, &b"<html></html>"[..]);
+
+    assert!(ASSETS.get("/missing").is_none());
+    assert_eq!(ASSETS.paths().collect::<Vec<_>>(), ["/app.js", "/index.html", "/style.css"]);
+}
+
+#[test]
+fn should_stream_lines_across_frames() {
+    use http_fancy::body::{LineStream, LineStreamError};
+
+    fn lines(body: FramesBody, max: usize) -> Vec<Result<bytes::Bytes, LineStreamError<core::convert::Infallible>>> {
+        let waker = waker::create(should_not_call_waker);
+        let mut ctx = task::Context::from_waker(&waker);
+        let mut lines = LineStream::new(body, max);
+        let mut result = Vec::new();
+        while let task::Poll::Ready(Some(line)) = Pin::new(&mut lines).poll_next(&mut ctx) {
+            result.push(line);
+        }
+        result
+    }
+
+    let result = lines(FramesBody::new(&[b"first\r\nsec", b"", b"ond\n\nthi", b"rd"]), 10);
+    let result = result.into_iter().map(|line| line.expect("line")).collect::<Vec<_>>();
+    assert_eq!(result, [&b"first"[..], b"second", b"", b"third"]);
+
+    let result = lines(FramesBody::new(&[b"ok\n"]), 10);
+    let result = result.into_iter().map(|line| line.expect("line")).collect::<Vec<_>>();
+    assert_eq!(result, [&b"ok"[..]]);
+
+    let result = lines(FramesBody::new(&[b"ok\nway", b"tooo", b"long\nnext\n"]), 4);
+    assert_eq!(result.len(), 2);
+    assert_eq!(result[0].as_ref().expect("line"), &b"ok"[..]);
+    assert!(matches!(result[1], Err(LineStreamError::LineTooLong)));
+}
+
+#[cfg(feature = "stream")]
+#[test]
+fn should_stream_lines_of_not_unpin_body() {
+    use http_fancy::body::{LineStream, Stream};
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    let mut lines = core::pin::pin!(LineStream::new(PinnedBody::new("first\r\nsecond"), 10));
+    let mut result = Vec::new();
+    while let task::Poll::Ready(Some(line)) = Stream::poll_next(lines.as_mut(), &mut ctx) {
+        result.push(line.expect("line"));
+    }
+    assert_eq!(result, [&b"first"[..], b"second"]);
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn should_collect_csv_with_quoted_fields_across_frames() {
+    use http_fancy::body::{Collector, CsvCollector, CsvError};
+
+    let body = FramesBody::new(&[b"name,quote\r\n", b"alice,\"said \"\"hi", b"\"\"\nthen left\"\n\nbob,", b"plain\n\"x,y\",\"\""]);
+    match call_future_once(Collect::<100, _, _>::new(body, CsvCollector::new().with_headers())) {
+        Ok(records) => {
+            assert_eq!(records.headers(), Some(&["name".to_owned(), "quote".to_owned()][..]));
+            assert_eq!(records.rows(), [
+                vec!["alice".to_owned(), "said \"hi\"\nthen left".to_owned()],
+                vec!["bob".to_owned(), "plain".to_owned()],
+                vec!["x,y".to_owned(), "".to_owned()],
+            ]);
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"a;b\n1;2"]), CsvCollector::new().delimiter(b';'))) {
+        Ok(records) => {
+            assert!(records.headers().is_none());
+            assert_eq!(records.rows(), [vec!["a".to_owned(), "b".to_owned()], vec!["1".to_owned(), "2".to_owned()]]);
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"a,b\n1,2,3\n"]), CsvCollector::new())) {
+        Err(CollectError::AppendError(CsvError::FieldCount { record: 2, expected: 2, actual: 3 })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"\"open"]), CsvCollector::new())) {
+        Err(CollectError::ConsumeError(CsvError::UnterminatedQuote)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"\"a\"b\n"]), CsvCollector::new())) {
+        Err(CollectError::AppendError(CsvError::InvalidQuote { record: 1 })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let mut collector = CsvCollector::new().with_headers();
+    assert!(collector.append(bytes::Bytes::from_static(b"a,b\n1,2\n\"open")).is_none());
+    assert!(matches!(collector.consume(), Err(CsvError::UnterminatedQuote)));
+    assert_eq!(collector.len(), 0);
+    assert!(collector.append(bytes::Bytes::from_static(b"x,y,z\n1,2,3")).is_none());
+    let records = collector.consume().expect("to consume");
+    assert_eq!(records.headers(), Some(&["x".to_owned(), "y".to_owned(), "z".to_owned()][..]));
+    assert_eq!(records.rows(), [vec!["1".to_owned(), "2".to_owned(), "3".to_owned()]]);
+}
+
+#[test]
+fn should_compute_memoized_cache_key() {
+    use http_fancy::body::{Body, CacheKeyBody};
+
+    assert_eq!(Body::empty().cache_key(), 0xcbf29ce484222325);
+    assert_eq!(Body::from("a").cache_key(), 0xaf63dc4c8601ec8c);
+    assert_eq!(Body::from("foobar").cache_key(), Body::from(String::from("foobar")).cache_key());
+    assert_ne!(Body::from("foobar").cache_key(), Body::from("foobaz").cache_key());
+
+    let body = CacheKeyBody::new(Body::from("foobar"));
+    let key = body.cache_key();
+    assert_eq!(key, 0x85944171f73967e8);
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 1);
+}
+
+#[test]
+fn should_distinguish_append_and_consume_errors() {
+    use http_fancy::body::{LineError, LineLengthCollector, SplitCollect, SplitError};
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"too long line"]), LineLengthCollector::new(Vec::new(), 4))) {
+        Err(error @ CollectError::AppendError(_)) => assert!(matches!(error.collector_error(), Some(LineError::LineTooLong))),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    match call_future_once(SplitCollect::<4, 100, _>::split(FramesBody::new(&[b"12"]))) {
+        Err(error @ CollectError::ConsumeError(_)) => assert!(matches!(error.collector_error(), Some(SplitError::TooShort { actual: 2 }))),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    assert!(CollectError::<(), ()>::Overflow(None).collector_error().is_none());
+}
+
+#[test]
+fn should_collect_string_validating_split_code_points() {
+    use http_fancy::body::{StringCollector, StringError};
+
+    //"añ€😀" with every multi-byte code point split across frames
+    let body = FramesBody::new(&[b"a\xc3", b"\xb1\xe2", b"\x82", b"\xac\xf0\x9f", b"\x98\x80"]);
+    match call_future_once(Collect::<100, _, _>::new(body, StringCollector::new())) {
+        Ok(text) => assert_eq!(text, "añ€😀"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"ab\xe2\x82"]), StringCollector::new())) {
+        Err(CollectError::ConsumeError(StringError::Incomplete { valid_up_to: 2 })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(text) => panic!("Unexpected result: {text}"),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"ab\xc3", b"(", b"more"]), StringCollector::new())) {
+        Err(CollectError::AppendError(StringError::InvalidUtf8 { valid_up_to: 2 })) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(text) => panic!("Unexpected result: {text}"),
+    }
+}
+
+#[test]
+fn should_emit_multipart_form_data() {
+    use http_fancy::body::{content_length, MultipartBody};
+
+    let mut body = MultipartBody::with_boundary("XyZ".to_owned()).expect("valid boundary");
+    body.add_field("title", "Hello \"world\"").expect("to add")
+        .add_file("upload", "a.txt", "text/plain", bytes::Bytes::from_static(b"file\r\ncontent")).expect("to add");
+    assert_eq!(body.content_type(), "multipart/form-data; boundary=XyZ");
+
+    let expected = "--XyZ\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nHello \"world\"\r\n\
+                    --XyZ\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"a.txt\"\r\nContent-Type: text/plain\r\n\r\nfile\r\ncontent\r\n\
+                    --XyZ--\r\n";
+    assert_eq!(content_length(&body), Some(expected.len() as u64));
+    match call_future_once(Collect::<1000, _, _>::new(body, Vec::new())) {
+        Ok(data) => assert_eq!(core::str::from_utf8(&data).unwrap(), expected),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut body = MultipartBody::new();
+    body.add_field("na\"me", "").expect("to add");
+    assert_ne!(MultipartBody::new().boundary(), body.boundary());
+    let expected = format!("--{0}\r\nContent-Disposition: form-data; name=\"na%22me\"\r\n\r\n\r\n--{0}--\r\n", body.boundary());
+    assert_eq!(content_length(&body), Some(expected.len() as u64));
+    match call_future_once(Collect::<1000, _, _>::new(body, Vec::new())) {
+        Ok(data) => assert_eq!(core::str::from_utf8(&data).unwrap(), expected),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_fail_body_emitted_past_deadline() {
+    use http_fancy::body::{EmitDeadlineBody, EmitDeadlineError, HttpBody};
+    use core::time::Duration;
+
+    let mut timeouts = Vec::new();
+    let body = EmitDeadlineBody::new(FramesBody::new(&[b"12", b"3"]), Duration::from_secs(5), |timeout| {
+        timeouts.push(timeout);
+        core::future::pending::<()>()
+    });
+    match call_future_once(Collect::<100, _, _>::new(body, Vec::new())) {
+        Ok(data) => assert_eq!(data, b"123"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert_eq!(timeouts, [Duration::from_secs(5)]);
+
+    //Deadline is checked even if body is always ready
+    let mut body = EmitDeadlineBody::new(FramesBody::new(&[b"12", b"3"]), Duration::from_secs(5), |_| core::future::ready(()));
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    match HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+        task::Poll::Ready(Some(Err(EmitDeadlineError::DeadlineExceeded))) => (),
+        _ => panic!("Expected deadline error"),
+    }
+    assert!(body.is_end_stream());
+    assert!(matches!(HttpBody::poll_frame(Pin::new(&mut body), &mut ctx), task::Poll::Ready(None)));
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_limit_decompressed_size() {
+    use http_fancy::body::{DecompressCollector, DecompressError};
+
+    let compressed: &'static [u8] = zstd::bulk::compress(&[b'a'; 4096], 9).expect("To encode").leak();
+
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[compressed]), DecompressCollector::new().with_limit(4096));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data.len(), 4096),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    //Decoder may buffer output until the end, hence limit can only be detected on consume
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[compressed]), DecompressCollector::new().with_limit(4095));
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Overflow) | CollectError::ConsumeError(DecompressError::Overflow)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data.len()),
+    }
+
+    let result = Collect::<100, _, _>::new("123456789".to_owned(), DecompressCollector::new().with_limit(8));
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Overflow)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_collect_header_block() {
+    use http_fancy::body::{HeaderBlockCollector, HeaderBlockError};
+
+    let body = FramesBody::new(&[b"Content-Type: text/plain\r", b"\nX-Id:  1 \r\nx-i", b"d: 2\n\r\n"]);
+    let result = Collect::<100, _, _>::new(body, HeaderBlockCollector::new());
+    match call_future_once(result) {
+        Ok(headers) => {
+            assert_eq!(headers.len(), 3);
+            assert_eq!(headers["content-type"], "text/plain");
+            assert_eq!(headers.get_all("x-id").iter().collect::<Vec<_>>(), ["1", "2"]);
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let result = Collect::<100, _, _>::new("A: 1\nB: 2".to_owned(), HeaderBlockCollector::new());
+    match call_future_once(result) {
+        Ok(headers) => assert_eq!(headers["b"], "2"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let cases = [
+        ("A: 1\nno colon\n", HeaderBlockError::InvalidLine { line: 2 }),
+        ("A: 1\n folded\n", HeaderBlockError::InvalidLine { line: 2 }),
+        ("Bad Name: 1\n", HeaderBlockError::InvalidName { line: 1 }),
+        ("A: \x7f\n", HeaderBlockError::InvalidValue { line: 1 }),
+        ("A: 1\n\nB: 2\n", HeaderBlockError::TrailingData { line: 3 }),
+    ];
+    for (body, expected) in cases {
+        let result = Collect::<100, _, _>::new(body.to_owned(), HeaderBlockCollector::new());
+        match call_future_once(result) {
+            Err(CollectError::AppendError(error)) => assert_eq!(error, expected),
+            Err(error) => panic!("Unexpected error: {error}"),
+            Ok(headers) => panic!("Unexpected result: {:?}", headers),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn should_stream_channel_body() {
+    use http_fancy::body::{ChannelBody, HttpBody};
+
+    let (sender, body) = ChannelBody::<&'static str>::channel();
+    assert!(body.size_hint().upper().is_none());
+    let producer = std::thread::spawn(move || {
+        sender.send(Ok(bytes::Bytes::from_static(b"12"))).expect("to send");
+        sender.send(Ok(bytes::Bytes::from_static(b"345"))).expect("to send");
+    });
+    producer.join().expect("producer to finish");
+
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1].data_ref().expect("data"), &b"345"[..]);
+
+    let (sender, body) = ChannelBody::channel();
+    sender.send(Ok(bytes::Bytes::from_static(b"12"))).expect("to send");
+    sender.send(Err("generator failed")).expect("to send");
+    match call_future_once(Collect::<100, _, _>::new(body, Vec::new())) {
+        Err(CollectError::Transport("generator failed")) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+    assert!(sender.is_closed());
+    assert!(sender.send(Ok(bytes::Bytes::new())).is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn should_apply_backpressure_in_bounded_channel_body() {
+    use http_fancy::body::{ChannelBody, HttpBody};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    struct Flag(AtomicBool);
+    impl std::task::Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    let (sender, mut body) = ChannelBody::<&'static str>::bounded(1);
+    sender.send(Ok(bytes::Bytes::from_static(b"12"))).expect("to send");
+    assert!(sender.send(Ok(bytes::Bytes::from_static(b"345"))).is_err());
+    assert!(!sender.is_closed());
+
+    let flag = Arc::new(Flag(AtomicBool::new(false)));
+    let waker = task::Waker::from(flag.clone());
+    let mut ctx = task::Context::from_waker(&waker);
+    let mut send = Box::pin(sender.send_async(Ok(bytes::Bytes::from_static(b"345"))));
+    assert!(send.as_mut().poll(&mut ctx).is_pending());
+    assert!(!flag.0.load(Ordering::SeqCst));
+
+    let body_waker = waker::create(should_not_call_waker);
+    let mut body_ctx = task::Context::from_waker(&body_waker);
+    match Pin::new(&mut body).poll_frame(&mut body_ctx) {
+        task::Poll::Ready(Some(Ok(frame))) => assert_eq!(frame.data_ref().expect("data"), &b"12"[..]),
+        _ => panic!("Expected first frame"),
+    }
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert!(send.as_mut().poll(&mut ctx).is_ready());
+    drop(send);
+    drop(sender);
+
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].data_ref().expect("data"), &b"345"[..]);
+
+    let (sender, body) = ChannelBody::<&'static str>::bounded(1);
+    drop(body);
+    assert!(call_future_once(Box::pin(sender.send_async(Ok(bytes::Bytes::new())))).is_err());
+}
+
+#[test]
+fn should_emit_body_from_chunks() {
+    use http_fancy::body::{Body, HttpBody};
+
+    let body = Body::from_chunks(vec![bytes::Bytes::from_static(b"12"), bytes::Bytes::new(), bytes::Bytes::from_static(b"345")]);
+    assert!(!body.is_end_stream());
+    assert_eq!(body.size_hint().exact(), Some(5));
+
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].data_ref().expect("data"), &b"12"[..]);
+    assert_eq!(frames[1].data_ref().expect("data"), &b"345"[..]);
+
+    assert!(Body::from_chunks(Vec::new()).is_end_stream());
+}
+
+#[test]
+fn should_emit_body_trailers() {
+    use http_fancy::body::{Body, HttpBody};
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("grpc-status", http::HeaderValue::from_static("0"));
+    let mut body = Body::from("123").with_trailers(trailers);
+    assert_eq!(body.size_hint().exact(), None);
+    assert_eq!(body.size_hint().lower(), 3);
+    assert_eq!(body.size_hint().upper(), None);
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    match HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+        task::Poll::Ready(Some(Ok(frame))) => assert_eq!(frame.into_data().expect("data"), "123"),
+        _ => panic!("Expected data frame"),
+    }
+    assert!(!body.is_end_stream());
+    assert_eq!(body.size_hint().exact(), None);
+    assert_eq!(body.size_hint().lower(), 0);
+    match HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+        task::Poll::Ready(Some(Ok(frame))) => assert_eq!(frame.into_trailers().expect("trailers")["grpc-status"], "0"),
+        _ => panic!("Expected trailers frame"),
+    }
+    assert!(body.is_end_stream());
+    assert!(matches!(HttpBody::poll_frame(Pin::new(&mut body), &mut ctx), task::Poll::Ready(None)));
+
+    //Trailers are emitted even without data
+    let frames = poll_frames(Body::empty().with_trailers(http::HeaderMap::new()));
+    assert_eq!(frames.len(), 1);
+    assert!(frames[0].is_trailers());
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_compress_body_with_zstd() {
+    use http_fancy::body::{CompressBody, DecompressCollector, HttpBody};
+
+    let data: &'static str = "compressible content ".repeat(100).leak();
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-checksum", http::HeaderValue::from_static("1"));
+    let body = CompressBody::new(FramesBody::new(&[&data.as_bytes()[..1000], &data.as_bytes()[1000..]]).with_trailers(trailers), 3).expect("create encoder");
+    assert!(!body.is_end_stream());
+
+    let frames = poll_frames(body);
+    let (trailers, frames) = frames.split_last().expect("frames");
+    assert_eq!(trailers.trailers_ref().expect("trailers")["x-checksum"], "1");
+
+    let compressed = frames.iter().flat_map(|frame| frame.data_ref().expect("data").to_vec()).collect::<Vec<u8>>();
+    assert!(compressed.len() < data.len());
+    match call_future_once(Collect::<4096, _, _>::new(http_fancy::body::Body::from(compressed), DecompressCollector::new())) {
+        Ok(decompressed) => assert_eq!(decompressed, data.as_bytes()),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn should_collect_into_writer() {
+    use http_fancy::body::WriteCollector;
+
+    struct FailingWriter;
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let body = FramesBody::new(&[b"123", b"45"]);
+    match call_future_once(Collect::<5, _, _>::new(body, WriteCollector::new(std::io::Cursor::new(Vec::new())))) {
+        Ok(writer) => assert_eq!(writer.into_inner(), b"12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[b"123", b"456"]);
+    match call_future_once(Collect::<5, _, _>::new(body, WriteCollector::new(Vec::new()))) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    match call_future_once(Collect::<5, _, _>::new("123".to_owned(), WriteCollector::new(FailingWriter))) {
+        Err(CollectError::AppendError(error)) => assert_eq!(error.kind(), std::io::ErrorKind::BrokenPipe),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(_) => panic!("Unexpected success"),
+    }
+}
+
+#[test]
+fn should_collect_into_bytes_mut() {
+    let body = FramesBody::new(&[b"123", b"45"]);
+    match call_future_once(Collect::<5, _, _>::new(body, bytes::BytesMut::new())) {
+        Ok(data) => assert_eq!(data, "12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    //Uniquely owned frame is taken over
+    let frame = bytes::Bytes::from(b"123".to_vec());
+    let ptr = frame.as_ptr();
+    match call_future_once(Collect::<5, _, _>::new(http_fancy::body::Body::new(frame), bytes::BytesMut::new())) {
+        Ok(data) => assert_eq!(data.as_ptr(), ptr),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_collect_with_runtime_limit() {
+    use http_fancy::body::CollectDyn;
+
+    let limit = "5".parse::<usize>().expect("number");
+    match call_future_once(CollectDyn::with_limit(FramesBody::new(&[b"123", b"45"]), Vec::new(), limit)) {
+        Ok(data) => assert_eq!(data, b"12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(CollectDyn::with_limit(FramesBody::new(&[b"123", b"456"]), Vec::new(), limit)) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    //Runtime limit cannot exceed const limit
+    match call_future_once(Collect::<4, _, _>::with_limit(FramesBody::new(&[b"123", b"45"]), Vec::new(), limit)) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_timeout_stalled_collect() {
+    use http_fancy::body::{CollectTimeout, Frame, HttpBody};
+    use core::time::Duration;
+
+    //Delivers data, then stalls forever
+    struct StallingBody(Option<bytes::Bytes>);
+
+    impl HttpBody for StallingBody {
+        type Data = bytes::Bytes;
+        type Error = core::convert::Infallible;
+
+        fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            match self.0.take() {
+                Some(data) => task::Poll::Ready(Some(Ok(Frame::data(data)))),
+                None => task::Poll::Pending,
+            }
+        }
+    }
+
+    let mut timeouts = Vec::new();
+    let collect = CollectTimeout::new(Collect::<100, _, _>::new(FramesBody::new(&[b"12", b"3"]), Vec::new()), Duration::from_secs(30), |timeout| {
+        timeouts.push(timeout);
+        core::future::ready(())
+    });
+    //Ready body wins over expired timer
+    match call_future_once(collect) {
+        Ok(data) => assert_eq!(data, b"123"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert!(timeouts.is_empty());
+
+    let collect = CollectTimeout::new(Collect::<100, _, _>::new(StallingBody(Some(bytes::Bytes::from_static(b"12"))), Vec::new()), Duration::from_secs(30), |timeout| {
+        timeouts.push(timeout);
+        core::future::ready(())
+    });
+    match call_future_once(collect) {
+        Err(CollectError::Timeout { received: 2 }) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+    assert_eq!(timeouts, [Duration::from_secs(30)]);
+
+    let collect = CollectTimeout::new(Collect::<100, _, _>::new(PendingBody, Vec::new()), Duration::from_secs(30), |_| core::future::pending::<()>());
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    let mut collect = core::pin::pin!(collect);
+    assert!(collect.as_mut().poll(&mut ctx).is_pending());
+}
+
+#[test]
+fn should_return_collect_parts() {
+    use http_fancy::body::{HttpBody, RopeCollector};
+
+    let mut collect = Collect::<100, _, _>::new(http_fancy::body::Body::from("12"), RopeCollector::new());
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    match Future::poll(Pin::new(&mut collect), &mut ctx) {
+        task::Poll::Ready(Ok(rope)) => assert_eq!(rope.len(), 2),
+        _ => panic!("Expected collected body"),
+    }
+    assert_eq!(http_fancy::body::Collector::len(collect.collector_mut()), 0);
+
+    let (body, _collector) = collect.into_parts();
+    assert!(body.is_end_stream());
+
+    //Collector is returned as it is before completion
+    let mut collect = Collect::<100, _, _>::new(PendingBody, Vec::new());
+    collect.collector_mut().extend_from_slice(b"1");
+    let (_, collector) = collect.into_parts();
+    assert_eq!(collector, b"1");
+}
+
+#[test]
+fn should_capture_trailers() {
+    use http_fancy::body::TrailersCollector;
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("grpc-status", http::HeaderValue::from_static("0"));
+    trailers.append("x-tag", http::HeaderValue::from_static("a"));
+    trailers.append("x-tag", http::HeaderValue::from_static("b"));
+    let body = FramesBody::new(&[b"123"]).with_trailers(trailers);
+    match call_future_once(Collect::<100, _, _>::new(body, TrailersCollector::new(Vec::new()))) {
+        Ok((data, Some(trailers))) => {
+            assert_eq!(data, b"123");
+            assert_eq!(trailers["grpc-status"], "0");
+            assert_eq!(trailers.get_all("x-tag").iter().count(), 2);
+        },
+        Ok((_, None)) => panic!("Trailers are missing"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[b"123"]), TrailersCollector::new(Vec::new()))) {
+        Ok((data, trailers)) => {
+            assert_eq!(data, b"123");
+            assert!(trailers.is_none());
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    //Multiple trailer frames are merged
+    let mut collector = TrailersCollector::new(Vec::new());
+    let mut first = http::HeaderMap::new();
+    first.insert("x-tag", http::HeaderValue::from_static("a"));
+    let mut second = http::HeaderMap::new();
+    second.append("x-tag", http::HeaderValue::from_static("b"));
+    second.append("x-tag", http::HeaderValue::from_static("c"));
+    second.insert("x-other", http::HeaderValue::from_static("1"));
+    http_fancy::body::Collector::on_trailers(&mut collector, first);
+    http_fancy::body::Collector::on_trailers(&mut collector, second);
+    match http_fancy::body::Collector::consume(&mut collector) {
+        Ok((_, Some(trailers))) => {
+            assert_eq!(trailers.get_all("x-tag").iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+            assert_eq!(trailers["x-other"], "1");
+        },
+        Ok((_, None)) => panic!("Trailers are missing"),
+        Err(error) => match error {},
+    }
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn should_hash_collected_body() {
+    use http_fancy::body::HashCollector;
+    use http_fancy::hash::Sha256;
+
+    let body = FramesBody::new(&[b"hello ", b"world"]);
+    match call_future_once(Collect::<100, _, _>::new(body, HashCollector::<Sha256>::new())) {
+        Ok(digest) => assert_eq!(digest[..], [
+            0xb9, 0x4d, 0x27, 0xb9, 0x93, 0x4d, 0x3e, 0x08, 0xa5, 0x2e, 0x52, 0xd7, 0xda, 0x7d, 0xab, 0xfa,
+            0xc4, 0x84, 0xef, 0xe3, 0x7a, 0x53, 0x80, 0xee, 0x90, 0x88, 0xf7, 0xac, 0xe2, 0xef, 0xcd, 0xe9,
+        ]),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    match call_future_once(Collect::<10, _, _>::new(FramesBody::new(&[b"hello ", b"world"]), HashCollector::<Sha256>::new())) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(digest) => panic!("Unexpected result: {:?}", digest),
+    }
+}
+
+#[test]
+fn should_discard_body_counting_bytes() {
+    let body = FramesBody::new(&[b"hello ", b"", b"world"]).with_trailers(http::HeaderMap::new());
+    match call_future_once(Collect::<100, _, _>::new(body, http_fancy::body::DiscardCollector::new())) {
+        Ok(len) => assert_eq!(len, 11),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[b"hello ", b"world"]);
+    match call_future_once(Collect::<10, _, _>::new(body, http_fancy::body::DiscardCollector::new())) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(len) => panic!("Unexpected result: {len}"),
+    }
+}
+
+#[test]
+fn should_inspect_body_content() {
+    use http_fancy::body::Body;
+
+    let mut body = Body::from("12");
+    assert_eq!(body.len(), 2);
+    assert!(!body.is_empty());
+    assert_eq!(body.as_bytes(), "12");
+
+    let frames = poll_frames(&mut body);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(body.len(), 0);
+    assert!(body.is_empty());
+    assert!(Body::empty().is_empty());
+}
+
+#[test]
+fn should_resend_cloned_body() {
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-checksum", http::HeaderValue::from_static("1"));
+    let body = http_fancy::body::Body::from("12").with_trailers(trailers);
+    let retry = body.clone();
+
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 2);
+    let retry_frames = poll_frames(retry);
+    assert_eq!(retry_frames.len(), 2);
+    assert_eq!(retry_frames[0].data_ref().unwrap(), "12");
+    assert_eq!(retry_frames[1].trailers_ref().unwrap()["x-checksum"], "1");
+}
+
+#[test]
+fn should_chain_bodies() {
+    use http_fancy::body::{Body, ChainBody, HttpBody};
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-checksum", http::HeaderValue::from_static("1"));
+    let body = ChainBody::new(FramesBody::new(&[b"pre", b"amble"]).with_trailers(http::HeaderMap::new()), Body::from("body").with_trailers(trailers));
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 4);
+    assert_eq!(frames[0].data_ref().unwrap(), "pre");
+    assert_eq!(frames[1].data_ref().unwrap(), "amble");
+    assert_eq!(frames[2].data_ref().unwrap(), "body");
+    assert_eq!(frames[3].trailers_ref().unwrap()["x-checksum"], "1");
+
+    let body = ChainBody::new(Body::from("12"), Body::from("345"));
+    assert_eq!(body.size_hint().exact(), Some(5));
+    let body = ChainBody::new(FramesBody::new(&[b"12"]), Body::from("345"));
+    assert_eq!(body.size_hint().lower(), 3);
+    assert_eq!(body.size_hint().upper(), None);
+
+    let result = Collect::<10, _, _>::new(ChainBody::new(Body::from("12"), Body::from("345")), Vec::new());
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn should_decompress_lz4_across_frames() {
+    use http_fancy::body::{DecompressCollector, DecompressError, Encoding};
+
+    //Content size and content checksum
+    const LZ4: [u8; 65] = [
+        4, 34, 77, 24, 108, 64, 70, 0, 0, 0, 0, 0, 0, 0, 224, 38, 0, 0, 0, 110, 104, 101, 108, 108, 111, 32, 6, 0, 175, 108, 122, 52, 32, 119, 111,
+        114, 108, 100, 44, 29, 0, 0, 2, 47, 0, 176, 108, 122, 52, 32, 119, 111, 114, 108, 100, 33, 10, 0, 0, 0, 0, 172, 215, 213, 7,
+    ];
+    //Block checksum and content checksum
+    const LZ4_BLOCK_CHECKSUM: [u8; 61] = [
+        4, 34, 77, 24, 116, 64, 189, 38, 0, 0, 0, 110, 104, 101, 108, 108, 111, 32, 6, 0, 175, 108, 122, 52, 32, 119, 111, 114, 108, 100, 44, 29, 0,
+        0, 2, 47, 0, 176, 108, 122, 52, 32, 119, 111, 114, 108, 100, 33, 10, 197, 185, 248, 63, 0, 0, 0, 0, 172, 215, 213, 7,
+    ];
+    let expected = "hello hello hello hello lz4 world, hello hello hello hello lz4 world!\n".repeat(2);
+
+    let body = FramesBody::new(&[&LZ4[..3], &LZ4[3..20], &LZ4[20..60], &LZ4[60..], &LZ4_BLOCK_CHECKSUM[..30], &LZ4_BLOCK_CHECKSUM[30..]]);
+    let result = Collect::<200, _, _>::new(body, DecompressCollector::new().require(Encoding::Lz4));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, expected.as_bytes()),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    for idx in [30, 59] {
+        let mut corrupted = LZ4_BLOCK_CHECKSUM;
+        corrupted[idx] ^= 1;
+        let corrupted: &'static [u8] = corrupted.to_vec().leak();
+        let body = FramesBody::new(&[corrupted]);
+        let result = Collect::<200, _, _>::new(body, DecompressCollector::new());
+        match call_future_once(result) {
+            Err(CollectError::AppendError(DecompressError::Lz4(_))) => (),
+            Err(error) => panic!("Unexpected error: {error}"),
+            Ok(data) => panic!("Unexpected result: {:?}", data),
+        }
+    }
+
+    let body = FramesBody::new(&[&LZ4[..40]]);
+    let result = Collect::<200, _, _>::new(body, DecompressCollector::new());
+    match call_future_once(result) {
+        Err(CollectError::ConsumeError(DecompressError::Lz4(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    //Skippable frames only
+    const SKIPPABLE: [u8; 11] = [0x50, 0x2a, 0x4d, 0x18, 3, 0, 0, 0, b'x', b'y', b'z'];
+    let body = FramesBody::new(&[&SKIPPABLE[..6], &SKIPPABLE[6..], &SKIPPABLE]);
+    let collector = DecompressCollector::with_encoding(Encoding::Lz4).expect("to create decoder");
+    match call_future_once(Collect::<200, _, _>::new(body, collector)) {
+        Ok(data) => assert!(data.is_empty()),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    //Skippable frame of maximum size is not buffered
+    let body = FramesBody::new(&[&[0x5f, 0x2a, 0x4d, 0x18, 0xff, 0xff, 0xff, 0xff], &[0; 100]]);
+    let collector = DecompressCollector::with_encoding(Encoding::Lz4).expect("to create decoder");
+    match call_future_once(Collect::<200, _, _>::new(body, collector)) {
+        Err(CollectError::ConsumeError(DecompressError::Lz4(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    //Second block references first one, which is allowed only for linked blocks
+    fn linked_blocks(flags: u8, checksum: u8) -> &'static [u8] {
+        let mut frame = vec![4, 34, 77, 24, flags, 0x40, checksum];
+        frame.extend_from_slice(&[4, 0, 0, 0x80, b'a', b'b', b'c', b'd']);
+        frame.extend_from_slice(&[5, 0, 0, 0, 0x00, 4, 0, 0x10, b'!']);
+        frame.extend_from_slice(&[0, 0, 0, 0]);
+        frame.leak()
+    }
+    let collector = DecompressCollector::with_encoding(Encoding::Lz4).expect("to create decoder");
+    match call_future_once(Collect::<200, _, _>::new(FramesBody::new(&[linked_blocks(0x40, 192)]), collector)) {
+        Ok(data) => assert_eq!(data, b"abcdabcd!"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    let collector = DecompressCollector::with_encoding(Encoding::Lz4).expect("to create decoder");
+    match call_future_once(Collect::<200, _, _>::new(FramesBody::new(&[linked_blocks(0x60, 130)]), collector)) {
+        Err(CollectError::AppendError(DecompressError::Lz4(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn should_decompress_deflate_with_explicit_encoding() {
+    use http_fancy::body::{DecompressCollector, DecompressError, Encoding};
+
+    const ZLIB: [u8; 19] = [120, 218, 75, 73, 77, 203, 73, 44, 73, 85, 72, 65, 165, 21, 1, 113, 152, 8, 225];
+    let collector = DecompressCollector::with_encoding(Encoding::Deflate).expect("to create decoder");
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZLIB[..1], &ZLIB[1..]]), collector);
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"deflate deflate deflate!"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    //zlib is single stream
+    let collector = DecompressCollector::with_encoding(Encoding::Deflate).expect("to create decoder");
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZLIB, &ZLIB]), collector);
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Gzip(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    //Raw DEFLATE stream without zlib wrapper
+    let collector = DecompressCollector::with_encoding(Encoding::Deflate).expect("to create decoder");
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZLIB[2..15]]), collector);
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Gzip(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_count_frames_seen() {
+    let body = FramesBody::new(&[b"1", b"", b"23", b"4"]).with_trailers(http::HeaderMap::new());
+    let mut result = Collect::<10, _, _>::new(body, Vec::new());
+    assert_eq!(result.frames_seen(), 0);
+    match call_future_once(&mut result) {
+        Ok(data) => assert_eq!(data, b"1234"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert_eq!(result.frames_seen(), 3);
+    assert_eq!(result.received(), 4);
+}
+
+#[test]
+fn should_limit_number_of_frames() {
+    let body = FramesBody::new(&[b"1", b"", b"2", b"", b"3"]);
+    let result = Collect::<10, _, _>::new(body, Vec::new()).with_max_frames(3);
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"123"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[b"1", b"2", b"3", b"4"]);
+    let mut result = Collect::<10, _, _>::new(body, Vec::new()).with_max_frames(3);
+    match call_future_once(&mut result) {
+        Err(CollectError::TooManyFrames) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+    assert_eq!(result.frames_seen(), 3);
+}
+
+#[test]
+fn should_access_body_as_slice() {
+    fn starts_with_brace(data: impl AsRef<[u8]>) -> bool {
+        data.as_ref().first() == Some(&b'{')
+    }
+
+    let body = http_fancy::body::Body::from("{}");
+    assert!(starts_with_brace(&body));
+    assert_eq!(&body[..], b"{}");
+    assert_eq!(body.iter().filter(|byte| **byte == b'}').count(), 1);
+}
+
+#[test]
+fn should_keep_partial_data_on_overflow() {
+    use http_fancy::body::Collector;
+
+    let body = FramesBody::new(&[b"12", b"34", b"56"]);
+    let mut collect = Collect::<5, _, _>::new(body, Vec::new());
+    match call_future_once(&mut collect) {
+        Err(CollectError::Overflow(Some(partial))) => assert_eq!(partial, b"12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+    assert_eq!(collect.received(), 5);
+    assert!(collect.collector_mut().is_empty());
+
+    //Collector rejects prefix of overflowing frame
+    let body = FramesBody::new(&[b"12", b"\xff\xfe"]);
+    match call_future_once(Collect::<3, _, _>::new(body, http_fancy::body::StringCollector::new())) {
+        Err(CollectError::AppendError(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    //Collector cannot produce output from partial data
+    let body = FramesBody::new(&[b"12", b"\xe2\x82\xac"]);
+    match call_future_once(Collect::<3, _, _>::new(body, http_fancy::body::StringCollector::new())) {
+        Err(CollectError::Overflow(None)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let headers = http::HeaderMap::from_iter([(http::header::CONTENT_LENGTH, http::HeaderValue::from_static("6"))]);
+    match call_future_once(Collect::<5, _, _>::from_headers(&headers, FramesBody::new(&[b"123456"]), Vec::new())) {
+        Err(CollectError::Overflow(None)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_detect_mime_while_collecting() {
+    use http_fancy::body::MimeSniffCollector;
+
+    let body = FramesBody::new(&[b"\x89PN", b"G\r\n\x1a\n\0\0\0\rIHDR", b"rest"]);
+    match call_future_once(Collect::<100, _, _>::new(body, MimeSniffCollector::new(Vec::new()))) {
+        Ok((data, mime)) => {
+            assert_eq!(data.len(), 20);
+            assert_eq!(mime, Some("image/png"));
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[b" \n{\"key\":", b" \"value\"}"]);
+    match call_future_once(Collect::<100, _, _>::new(body, MimeSniffCollector::new(Vec::new()))) {
+        Ok((data, mime)) => {
+            assert_eq!(data, b" \n{\"key\": \"value\"}");
+            assert_eq!(mime, Some("application/json"));
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let text: &'static [u8] = "caf\u{e9} ".repeat(200).into_bytes().leak();
+    let body = FramesBody::new(&[&text[..3], &text[3..]]);
+    match call_future_once(Collect::<2000, _, _>::new(body, MimeSniffCollector::new(Vec::new()))) {
+        Ok((data, mime)) => {
+            assert_eq!(data, text);
+            assert_eq!(mime, Some("text/plain"));
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    for text in [&b"BMW owners club meeting notes"[..], b"ID3 tags are stored at the start of file"] {
+        let body = FramesBody::new(&[&text[..5], &text[5..]]);
+        match call_future_once(Collect::<100, _, _>::new(body, MimeSniffCollector::new(Vec::new()))) {
+            Ok((data, mime)) => {
+                assert_eq!(data, text);
+                assert_eq!(mime, Some("text/plain"));
+            },
+            Err(error) => panic!("Unexpected error: {error}"),
+        }
+    }
+
+    let body = FramesBody::new(&[b"\x00\x01\x02"]);
+    match call_future_once(Collect::<100, _, _>::new(body, MimeSniffCollector::new(Vec::new()))) {
+        Ok((_, mime)) => assert_eq!(mime, None),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_reclaim_body_bytes() {
+    let body = http_fancy::body::Body::from(vec![1, 2, 3]).with_trailers(http::HeaderMap::new());
+    assert_eq!(body.into_bytes(), &[1, 2, 3][..]);
+
+    let mut body = http_fancy::body::Body::from("12");
+    let _ = poll_frames(&mut body);
+    assert!(body.into_bytes().is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn should_create_body_from_reader() {
+    let mut reader = std::io::Cursor::new(b"upload".to_vec());
+    let body = http_fancy::body::Body::from_reader(&mut reader).expect("to read");
+    assert_eq!(body.as_bytes(), "upload");
+    assert_eq!(http_fancy::body::content_length(&body), Some(6));
+}
+
+struct PinnedBody {
+    body: http_fancy::body::Body,
+    _pinned: core::marker::PhantomPinned,
+}
+
+impl PinnedBody {
+    fn new(data: &'static str) -> Self {
+        Self {
+            body: http_fancy::body::Body::from(data),
+            _pinned: core::marker::PhantomPinned,
+        }
+    }
+}
+
+impl http_fancy::body::HttpBody for PinnedBody {
+    type Data = bytes::Bytes;
+    type Error = core::convert::Infallible;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+        let body = unsafe {
+            self.map_unchecked_mut(|this| &mut this.body)
+        };
+        http_fancy::body::HttpBody::poll_frame(body, cx)
+    }
+}
+
+#[test]
+fn should_collect_not_unpin_body() {
+    let new_body = || PinnedBody::new("pinned");
+    let collect = core::pin::pin!(Collect::<10, _, _>::new(new_body(), Vec::new()));
+    match call_future_once(collect) {
+        Ok(data) => assert_eq!(data, b"pinned"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut is_called = false;
+    let collect = core::pin::pin!(Collect::<10, _, _>::new(http_fancy::body::TakeBody::new(new_body(), 3), Vec::new()).before_first_frame(|| is_called = true));
+    match call_future_once(collect) {
+        Ok(data) => assert_eq!(data, b"pin"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    assert!(is_called);
+
+    let collect = core::pin::pin!(Collect::<10, _, _>::new(new_body(), Vec::new()).inspect(|data| assert_eq!(data, b"pinned")));
+    assert!(call_future_once(collect).is_ok());
+
+    let collect = http_fancy::body::CollectAll::<10, _, _, _, _>::new([new_body(), new_body()], Vec::new, 2);
+    for result in call_future_once(collect) {
+        match result {
+            Ok(data) => assert_eq!(data, b"pinned"),
+            Err(error) => panic!("Unexpected error: {error}"),
+        }
+    }
+}
+
+#[test]
+fn should_map_data_frames() {
+    use http_fancy::body::{HttpBody, MapBody};
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-checksum", http::HeaderValue::from_static("1"));
+    let body = MapBody::new(FramesBody::new(&[b"line\r\n", b"end"]).with_trailers(trailers), |data: bytes::Bytes| {
+        bytes::Bytes::from(data.to_ascii_uppercase())
+    });
+    assert_eq!(body.size_hint().upper(), None);
+
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[0].data_ref().unwrap(), "LINE\r\n");
+    assert_eq!(frames[1].data_ref().unwrap(), "END");
+    assert_eq!(frames[2].trailers_ref().unwrap()["x-checksum"], "1");
+}
+
+#[test]
+fn should_rechunk_body() {
+    use http_fancy::body::{Body, HttpBody, RechunkBody};
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-checksum", http::HeaderValue::from_static("1"));
+    let body = RechunkBody::new(FramesBody::new(&[b"1", b"23", b"456789a", b"b"]).with_trailers(trailers), 4);
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 4);
+    assert_eq!(frames[0].data_ref().unwrap(), "1234");
+    assert_eq!(frames[1].data_ref().unwrap(), "5678");
+    assert_eq!(frames[2].data_ref().unwrap(), "9ab");
+    assert_eq!(frames[3].trailers_ref().unwrap()["x-checksum"], "1");
+
+    let body = RechunkBody::new(Body::from("123456"), 4);
+    assert_eq!(body.size_hint().exact(), Some(6));
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1].data_ref().unwrap(), "56");
+}
+
+#[test]
+fn should_report_collector_emptiness() {
+    use http_fancy::body::{Collector, DiscardCollector, RopeCollector};
+
+    let mut collector = DiscardCollector::new();
+    assert!(collector.is_empty());
+    assert!(collector.append(bytes::Bytes::from_static(b"1")).is_none());
+    assert!(!collector.is_empty());
+
+    let mut collector = RopeCollector::new();
+    assert!(Collector::is_empty(&collector));
+    assert!(collector.append(bytes::Bytes::from_static(b"1")).is_none());
+    assert!(!Collector::is_empty(&collector));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn should_convert_collect_error_into_io() {
+    use std::error::Error;
+    use http_fancy::body::{Body, StringCollector, StringError};
+
+    fn collect(body: Body) -> std::io::Result<Vec<u8>> {
+        Ok(call_future_once(Collect::<2, _, _>::new(body, Vec::new()))?)
+    }
+
+    match collect(Body::from("123")) {
+        Err(error) => {
+            assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+            assert_eq!(error.to_string(), "Overflow");
+        },
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let error = match call_future_once(Collect::<10, _, _>::new(Body::from(vec![0xff]), StringCollector::new())) {
+        Err(error) => error,
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    };
+    let source = error.source().and_then(|error| error.downcast_ref::<StringError>());
+    assert_eq!(source, Some(&StringError::InvalidUtf8 { valid_up_to: 0 }));
+
+    let error = error.into_io();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    assert!(error.get_ref().and_then(|error| error.downcast_ref::<StringError>()).is_some());
+
+    let error = CollectError::<std::io::Error, core::convert::Infallible>::Transport(std::io::ErrorKind::ConnectionReset.into());
+    assert_eq!(error.into_io().kind(), std::io::ErrorKind::ConnectionReset);
+}
+
+#[test]
+fn should_repeat_pattern() {
+    use http_fancy::body::{HttpBody, RepeatBody};
+
+    let body = RepeatBody::new(bytes::Bytes::from_static(b"abc"), 3);
+    assert_eq!(body.size_hint().exact(), Some(9));
+    assert!(!body.is_end_stream());
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 3);
+    assert!(frames.iter().all(|frame| frame.data_ref().unwrap() == "abc"));
+
+    let body = RepeatBody::new(bytes::Bytes::new(), 3);
+    assert!(body.is_end_stream());
+    assert_eq!(body.size_hint().exact(), Some(0));
+    assert!(poll_frames(body).is_empty());
+}
+
+#[test]
+fn should_limit_number_of_lines() {
+    use http_fancy::body::{LineError, LineLimitCollector};
+
+    let body = FramesBody::new(&[b"KEY=1\nOTH", b"ER=2\n", b"\nLAST=3"]);
+    match call_future_once(Collect::<100, _, _>::new(body, LineLimitCollector::new(Vec::new(), 4))) {
+        Ok(data) => assert_eq!(data, b"KEY=1\nOTHER=2\n\nLAST=3"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = FramesBody::new(&[b"KEY=1\nOTH", b"ER=2\n", b"\nLAST=3"]);
+    match call_future_once(Collect::<100, _, _>::new(body, LineLimitCollector::new(Vec::new(), 3))) {
+        Err(CollectError::AppendError(LineError::TooManyLines)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let body = FramesBody::new(&[b"1\n", b"2\n", b"3\n"]);
+    match call_future_once(Collect::<100, _, _>::new(body, LineLimitCollector::new(Vec::new(), 3))) {
+        Ok(data) => assert_eq!(data, b"1\n2\n3\n"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_decompress_zstd_with_large_window() {
+    use http_fancy::body::{Collector, DecompressCollector, DecompressError, Encoding};
+
+    //Compressed with `--long=28`
+    const ZSTD: [u8; 37] = [
+        40, 181, 47, 253, 4, 144, 193, 0, 0, 108, 111, 110, 103, 32, 119, 105, 110, 100, 111, 119, 32, 122, 115, 116, 100, 32, 112, 97, 121, 108, 111, 97,
+        100, 73, 153, 22, 95,
+    ];
+
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZSTD]), DecompressCollector::new());
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Zstd(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZSTD]), DecompressCollector::new().with_zstd_window_log_max(28));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"long window zstd payload"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    //Window applies to explicitly declared encoding
+    let collector = DecompressCollector::with_encoding(Encoding::Zstd).expect("zstd").with_zstd_window_log_max(28);
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZSTD[..10], &ZSTD[10..]]), collector);
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"long window zstd payload"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let collector = DecompressCollector::with_encoding(Encoding::Zstd).expect("zstd").with_zstd_window_log_max(u32::MAX);
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZSTD]), collector);
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Zstd(_))) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    //Snapshot keeps configuration
+    let mut collector = DecompressCollector::new().with_zstd_window_log_max(28).with_limit(100);
+    assert!(collector.append(bytes::Bytes::from_static(&ZSTD[..2])).is_none());
+    let collector = DecompressCollector::restore(collector.snapshot().expect("to snapshot"));
+    let result = Collect::<100, _, _>::new(FramesBody::new(&[&ZSTD[2..]]), collector);
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"long window zstd payload"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut collector = DecompressCollector::new().require(Encoding::Zstd);
+    assert!(collector.append(bytes::Bytes::from_static(b"12")).is_none());
+    let mut collector = DecompressCollector::restore(collector.snapshot().expect("to snapshot"));
+    assert!(matches!(collector.append(bytes::Bytes::from_static(b"345")), Some(DecompressError::EncodingMismatch { .. })));
+    let mut collector = DecompressCollector::new().with_limit(3);
+    assert!(collector.append(bytes::Bytes::from_static(b"12")).is_none());
+    let mut collector = DecompressCollector::restore(collector.snapshot().expect("to snapshot"));
+    assert!(matches!(collector.append(bytes::Bytes::from_static(b"345")), Some(DecompressError::Overflow)));
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_decompress_body_as_it_streams() {
+    use http_fancy::body::DecompressBody;
+
+    let expected = b"streamed zstd body, ".repeat(1000);
+    let compressed: &'static [u8] = zstd::bulk::compress(&expected, 9).expect("To encode").leak();
+    let (first, rest) = compressed.split_at(2);
+    let (second, third) = rest.split_at(rest.len() / 2);
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-checksum", http::HeaderValue::from_static("1"));
+    let body = FramesBody::new(&[first, second, third]).with_trailers(trailers.clone());
+
+    let mut data = Vec::new();
+    let mut frames = poll_frames(DecompressBody::new(body)).into_iter();
+    for frame in frames.by_ref() {
+        match frame.into_data() {
+            Ok(chunk) => data.extend_from_slice(&chunk),
+            Err(frame) => {
+                assert_eq!(frame.into_trailers().expect("trailers"), trailers);
+                break;
+            }
+        }
+    }
+    assert!(frames.next().is_none());
+    assert_eq!(data, expected);
+
+    let body = FramesBody::new(&[b"pl", b"ain text"]);
+    let frames = poll_frames(DecompressBody::new(body));
+    let data: Vec<u8> = frames.into_iter().flat_map(|frame| frame.into_data().expect("data")).collect();
+    assert_eq!(data, b"plain text");
+
+    let body = FramesBody::new(&[b"a"]);
+    let frames = poll_frames(DecompressBody::new(body));
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].data_ref().expect("data").as_ref(), b"a");
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn should_fail_decompress_body_on_corrupted_gzip() {
+    use http_fancy::body::{DecompressBody, DecompressBodyError, DecompressError, HttpBody};
+
+    const GZIP: [u8; 46] = [
+        31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 203, 72, 205, 201, 201, 87, 200, 64, 34, 211, 171, 50, 11, 20, 202, 243, 139, 114, 82, 116, 144, 197,
+        21, 51, 104, 160, 18, 0, 92, 58, 1, 241, 126, 0, 0, 0,
+    ];
+    let expected = "hello hello hello gzip world, hello hello!".repeat(3);
+
+    let body = FramesBody::new(&[&GZIP[..3], &GZIP[3..20], &GZIP[20..40], &GZIP[40..]]);
+    let frames = poll_frames(DecompressBody::new(body));
+    let data: Vec<u8> = frames.into_iter().flat_map(|frame| frame.into_data().expect("data")).collect();
+    assert_eq!(data, expected.as_bytes());
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+
+    let mut corrupted = GZIP;
+    corrupted[38] ^= 1;
+    let corrupted: &'static [u8] = corrupted.to_vec().leak();
+    let mut body = DecompressBody::new(FramesBody::new(&[corrupted]));
+    loop {
+        match HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+            task::Poll::Ready(Some(Ok(frame))) => assert!(frame.is_data()),
+            task::Poll::Ready(Some(Err(DecompressBodyError::Decompress(DecompressError::Gzip(_))))) => break,
+            task::Poll::Ready(Some(Err(error))) => panic!("Unexpected error: {error}"),
+            task::Poll::Ready(None) => panic!("Unexpected end of body"),
+            task::Poll::Pending => panic!("Unexpected pending"),
+        }
+    }
+    assert!(body.is_end_stream());
+    assert!(matches!(HttpBody::poll_frame(Pin::new(&mut body), &mut ctx), task::Poll::Ready(None)));
+}
+
+#[cfg(any(feature = "compress", feature = "gzip"))]
+#[test]
+fn should_end_decompress_body_on_transport_error() {
+    use http_fancy::body::{DecompressBody, DecompressBodyError, HttpBody};
+
+    struct ErrorOnceBody(bool);
+
+    impl HttpBody for ErrorOnceBody {
+        type Data = bytes::Bytes;
+        type Error = &'static str;
+
+        fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+            assert!(!self.0, "body is polled after error");
+            self.0 = true;
+            task::Poll::Ready(Some(Err("reset")))
+        }
+    }
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    let mut body = DecompressBody::new(ErrorOnceBody(false));
+    assert!(matches!(HttpBody::poll_frame(Pin::new(&mut body), &mut ctx), task::Poll::Ready(Some(Err(DecompressBodyError::Inner("reset"))))));
+    assert!(body.is_end_stream());
+    assert!(matches!(HttpBody::poll_frame(Pin::new(&mut body), &mut ctx), task::Poll::Ready(None)));
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn should_decompress_lz4_body_as_it_streams() {
+    use http_fancy::body::DecompressBody;
+
+    const LZ4: [u8; 65] = [
+        4, 34, 77, 24, 108, 64, 70, 0, 0, 0, 0, 0, 0, 0, 224, 38, 0, 0, 0, 110, 104, 101, 108, 108, 111, 32, 6, 0, 175, 108, 122, 52, 32, 119, 111,
+        114, 108, 100, 44, 29, 0, 0, 2, 47, 0, 176, 108, 122, 52, 32, 119, 111, 114, 108, 100, 33, 10, 0, 0, 0, 0, 172, 215, 213, 7,
+    ];
+    let expected = "hello hello hello hello lz4 world, hello hello hello hello lz4 world!\n";
+
+    let body = FramesBody::new(&[&LZ4[..3], &LZ4[3..20], &LZ4[20..60], &LZ4[60..]]);
+    let frames = poll_frames(DecompressBody::new(body));
+    let data: Vec<u8> = frames.into_iter().flat_map(|frame| frame.into_data().expect("data")).collect();
+    assert_eq!(data, expected.as_bytes());
+}
+
+#[test]
+fn should_collect_body_with_non_bytes_data() {
+    struct SliceBody(std::collections::VecDeque<&'static [u8]>);
+
+    impl http_fancy::body::HttpBody for SliceBody {
+        type Data = &'static [u8];
+        type Error = core::convert::Infallible;
+
+        fn poll_frame(mut self: Pin<&mut Self>, _: &mut task::Context<'_>) -> task::Poll<Option<Result<http_fancy::body::Frame<Self::Data>, Self::Error>>> {
+            task::Poll::Ready(self.0.pop_front().map(|data| Ok(http_fancy::body::Frame::data(data))))
+        }
+    }
+
+    let body = SliceBody([&b"12"[..], b"", b"345"].into());
+    let result = Collect::<5, _, _>::new(body, Vec::new());
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let body = SliceBody([&b"12"[..], b"345"].into());
+    let result = Collect::<4, _, _>::new(body, Vec::new());
+    match call_future_once(result) {
+        Err(CollectError::Overflow(_)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+}
+
+#[test]
+fn should_collect_into_fixed_buffer() {
+    use http_fancy::body::ArrayCollector;
+
+    let mut buffer = [0u8; 8];
+    let body = FramesBody::new(&[b"123", b"", b"45"]);
+    let result = Collect::<100, _, _>::new(body, ArrayCollector::new(&mut buffer));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, b"12345"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut buffer = [0u8; 4];
+    let body = FramesBody::new(&[b"123", b"45"]);
+    let mut result = Collect::<100, _, _>::new(body, ArrayCollector::new(&mut buffer));
+    match call_future_once(&mut result) {
+        Err(CollectError::AppendError(error)) => assert_eq!(error.capacity(), 4),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+    assert_eq!(result.collector_mut().filled(), b"123");
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_decompress_with_trailers() {
+    use http_fancy::body::{DecompressCollector, TrailersCollector};
+
+    let compressed: &'static [u8] = zstd::bulk::compress(b"grpc payload", 9).expect("To encode").leak();
+    let (first, second) = compressed.split_at(compressed.len() / 2);
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("grpc-status", http::HeaderValue::from_static("0"));
+    let body = FramesBody::new(&[first, second]).with_trailers(trailers.clone());
+    let result = Collect::<100, _, _>::new(body, TrailersCollector::new(DecompressCollector::new()));
+    match call_future_once(result) {
+        Ok((data, captured)) => {
+            assert_eq!(data, b"grpc payload");
+            assert_eq!(captured, Some(trailers));
+        },
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+}
+
+#[test]
+fn should_take_limited_bytes_of_body() {
+    use http_fancy::body::{HttpBody, TakeBody};
+
+    let body = TakeBody::new(FramesBody::new(&[b"123", b"456", b"789"]), 5);
+    assert_eq!(body.size_hint().lower(), 0);
+    assert_eq!(body.size_hint().upper(), Some(5));
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].data_ref().expect("data").as_ref(), b"123");
+    assert_eq!(frames[1].data_ref().expect("data").as_ref(), b"45");
+
+    let body = TakeBody::new(http_fancy::body::Body::from("123456789"), 4);
+    assert_eq!(body.size_hint().exact(), Some(4));
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].data_ref().expect("data").as_ref(), b"1234");
+
+    let body = TakeBody::new(http_fancy::body::Body::from("12"), 4);
+    assert_eq!(body.size_hint().exact(), Some(2));
+
+    let mut trailers = http::HeaderMap::new();
+    trailers.insert("x-trailer", http::HeaderValue::from_static("1"));
+    let body = TakeBody::new(FramesBody::new(&[b"12"]).with_trailers(trailers.clone()), 4);
+    let frames = poll_frames(body);
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[1].trailers_ref(), Some(&trailers));
+
+    let body = TakeBody::new(FramesBody::new(&[b"12"]), 0);
+    assert!(body.is_end_stream());
+    assert!(poll_frames(body).is_empty());
+}
+
+#[test]
+fn should_validate_multipart_form_data() {
+    use http_fancy::body::{HttpBody, MultipartBody, MultipartError};
+
+    for boundary in ["", "a\r\nb", "a\nb", "trailing ", "\"quoted\"", &"a".repeat(71)] {
+        assert_eq!(MultipartBody::with_boundary(boundary.to_owned()).err(), Some(MultipartError::InvalidBoundary), "{boundary:?}");
+    }
+    let body = MultipartBody::with_boundary("with space".to_owned()).expect("valid boundary");
+    assert_eq!(body.content_type(), "multipart/form-data; boundary=\"with space\"");
+    let body = MultipartBody::with_boundary("a".repeat(70)).expect("valid boundary");
+    assert_eq!(body.content_type(), format!("multipart/form-data; boundary={}", "a".repeat(70)).as_str());
+
+    let mut body = MultipartBody::with_boundary("XyZ".to_owned()).expect("valid boundary");
+    let content = bytes::Bytes::from_static(b"data");
+    assert_eq!(body.add_file("upload", "a.txt", "text/plain\r\n--XyZ", content.clone()).err(), Some(MultipartError::InvalidContentType));
+    assert!(body.add_file("upload", "a.txt", "text/plain; charset=utf-8", content).is_ok());
+    let forged = bytes::Bytes::from_static(b"data\r\n--XyZ\r\nContent-Disposition: form-data; name=\"admin\"\r\n\r\ntrue");
+    assert_eq!(body.add_file("upload", "a.txt", "text/plain", forged).err(), Some(MultipartError::BoundaryInContent));
+    assert_eq!(body.add_field("note", "--XyZ--").err(), Some(MultipartError::BoundaryInContent));
+    assert!(body.add_field("note", "XyZ -XyZ").is_ok());
+
+    let waker = waker::create(should_not_call_waker);
+    let mut ctx = task::Context::from_waker(&waker);
+    assert!(matches!(HttpBody::poll_frame(Pin::new(&mut body), &mut ctx), task::Poll::Ready(Some(Ok(_)))));
+    assert_eq!(body.add_field("late", "value").err(), Some(MultipartError::Finished));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn should_limit_gzip_output_while_decoding() {
+    use http_fancy::body::{Collector, DecompressCollector, DecompressError, Encoding};
+
+    //100000 zero bytes within single block
+    const PREFIX: [u8; 25] = [31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 237, 193, 49, 1, 0, 0, 0, 194, 160, 245, 79, 109, 13, 15, 160];
+    const SUFFIX: [u8; 11] = [128, 87, 3, 125, 149, 17, 212, 160, 134, 1, 0];
+    let mut gzip = PREFIX.to_vec();
+    gzip.extend_from_slice(&[0; 96]);
+    gzip.extend_from_slice(&SUFFIX);
+    let gzip: &'static [u8] = gzip.leak();
+
+    let result = Collect::<200, _, _>::new(FramesBody::new(&[gzip]), DecompressCollector::new());
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, [0; 100000]),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut result = Collect::<200, _, _>::new(FramesBody::new(&[gzip]), DecompressCollector::new().with_limit(1000));
+    match call_future_once(&mut result) {
+        Err(CollectError::AppendError(DecompressError::Overflow)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data.len()),
+    }
+    assert!(result.collector_mut().len() <= 1000);
+
+    //Output of exactly limit size is accepted, both for fixed and dynamic codes
+    let result = Collect::<200, _, _>::new(FramesBody::new(&[gzip]), DecompressCollector::new().with_limit(100000));
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data.len(), 100000),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    let result = Collect::<200, _, _>::new(FramesBody::new(&[gzip]), DecompressCollector::new().with_limit(99999));
+    match call_future_once(result) {
+        Err(CollectError::AppendError(DecompressError::Overflow)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data.len()),
+    }
+
+    const ZLIB: [u8; 19] = [120, 218, 75, 73, 77, 203, 73, 44, 73, 85, 72, 65, 165, 21, 1, 113, 152, 8, 225];
+    let collector = DecompressCollector::with_encoding(Encoding::Deflate).expect("to create decoder").with_limit(24);
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[&ZLIB]), collector)) {
+        Ok(data) => assert_eq!(data, b"deflate deflate deflate!"),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+    let collector = DecompressCollector::with_encoding(Encoding::Deflate).expect("to create decoder").with_limit(23);
+    match call_future_once(Collect::<100, _, _>::new(FramesBody::new(&[&ZLIB]), collector)) {
+        Err(CollectError::AppendError(DecompressError::Overflow)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data),
+    }
+
+    //Limit applies to encoding declared via Content-Encoding
+    let collector = DecompressCollector::from_content_encoding(&http::HeaderValue::from_static("gzip")).expect("gzip").with_limit(1000);
+    let mut result = Collect::<200, _, _>::new(FramesBody::new(&[gzip]), collector);
+    match call_future_once(&mut result) {
+        Err(CollectError::AppendError(DecompressError::Overflow)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data.len()),
+    }
+    assert!(result.collector_mut().len() <= 1000);
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn should_limit_lz4_output_while_decoding() {
+    use http_fancy::body::{Collector, DecompressCollector, DecompressError};
+
+    //60000 `x` bytes within single block
+    const PREFIX: [u8; 15] = [4, 34, 77, 24, 96, 64, 130, 246, 0, 0, 0, 31, 120, 1, 0];
+    const SUFFIX: [u8; 11] = [50, 80, 120, 120, 120, 120, 120, 0, 0, 0, 0];
+    let mut lz4 = PREFIX.to_vec();
+    lz4.extend_from_slice(&[255; 235]);
+    lz4.extend_from_slice(&SUFFIX);
+    let lz4: &'static [u8] = lz4.leak();
+
+    let result = Collect::<300, _, _>::new(FramesBody::new(&[lz4]), DecompressCollector::new());
+    match call_future_once(result) {
+        Ok(data) => assert_eq!(data, [b'x'; 60000]),
+        Err(error) => panic!("Unexpected error: {error}"),
+    }
+
+    let mut result = Collect::<300, _, _>::new(FramesBody::new(&[lz4]), DecompressCollector::new().with_limit(1000));
+    match call_future_once(&mut result) {
+        Err(CollectError::AppendError(DecompressError::Overflow)) => (),
+        Err(error) => panic!("Unexpected error: {error}"),
+        Ok(data) => panic!("Unexpected result: {:?}", data.len()),
+    }
+    assert!(result.collector_mut().len() <= 1000);
+}
+
+#[cfg(feature = "compress")]
+#[test]
+fn should_limit_decompress_body_output() {
+    use http_fancy::body::{DecompressBody, DecompressBodyError, DecompressError, HttpBody};
+
+    fn poll_until_error<T: HttpBody<Data = bytes::Bytes, Error = DecompressBodyError<core::convert::Infallible>> + Unpin>(mut body: T) -> (usize, DecompressError) {
+        let waker = waker::create(should_not_call_waker);
+        let mut ctx = task::Context::from_waker(&waker);
+        let mut len = 0;
+        loop {
+            match HttpBody::poll_frame(Pin::new(&mut body), &mut ctx) {
+                task::Poll::Ready(Some(Ok(frame))) => len += frame.into_data().expect("data").len(),
+                task::Poll::Ready(Some(Err(DecompressBodyError::Decompress(error)))) => break (len, error),
+                task::Poll::Ready(Some(Err(error))) => panic!("Unexpected error: {error}"),
+                task::Poll::Ready(None) => panic!("Unexpected end of body"),
+                task::Poll::Pending => panic!("Unexpected pending"),
+            }
+        }
+    }
+
+    let compressed: &'static [u8] = zstd::bulk::compress(&[0; 100000], 9).expect("To encode").leak();
+    let (first, second) = compressed.split_at(compressed.len() / 2);
+    let body = DecompressBody::new(FramesBody::new(&[first, second])).with_limit(1000);
+    assert!(matches!(poll_until_error(body), (0, DecompressError::Overflow)));
+
+    let body = DecompressBody::new(FramesBody::new(&[b"plain", b" text"])).with_limit(8);
+    assert!(matches!(poll_until_error(body), (5, DecompressError::Overflow)));
+
+    let body = FramesBody::new(&[b"plain", b" text"]);
+    let frames = poll_frames(DecompressBody::new(body).with_limit(10));
+    assert_eq!(frames.len(), 2);
+
+    #[cfg(feature = "gzip")]
+    {
+        //100000 zero bytes within single block
+        const PREFIX: [u8; 25] = [31, 139, 8, 0, 0, 0, 0, 0, 2, 3, 237, 193, 49, 1, 0, 0, 0, 194, 160, 245, 79, 109, 13, 15, 160];
+        const SUFFIX: [u8; 11] = [128, 87, 3, 125, 149, 17, 212, 160, 134, 1, 0];
+        let mut gzip = PREFIX.to_vec();
+        gzip.extend_from_slice(&[0; 96]);
+        gzip.extend_from_slice(&SUFFIX);
+        let gzip: &'static [u8] = gzip.leak();
+
+        let body = DecompressBody::new(FramesBody::new(&[gzip])).with_limit(1000);
+        assert!(matches!(poll_until_error(body), (0, DecompressError::Overflow)));
+
+        let frames = poll_frames(DecompressBody::new(FramesBody::new(&[gzip])).with_limit(100000));
+        assert_eq!(frames.iter().map(|frame| frame.data_ref().expect("data").len()).sum::<usize>(), 100000);
+        let body = DecompressBody::new(FramesBody::new(&[gzip])).with_limit(99999);
+        assert!(matches!(poll_until_error(body), (_, DecompressError::Overflow)));
+    }
+}